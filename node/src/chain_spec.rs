@@ -1,8 +1,9 @@
 use sp_core::{Pair, Public, sr25519};
 use node_wivsupplychain_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
-	SudoConfig, SystemConfig, WASM_BINARY, Signature
+	SudoConfig, SystemConfig, WivSupplyChainConfig, WASM_BINARY, Signature
 };
+use node_wivsupplychain_runtime::pallet_wivsupplychain::Role;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{Verify, IdentifyAccount};
@@ -151,7 +152,11 @@ fn testnet_genesis(
 		}),
 		pallet_sudo: Some(SudoConfig {
 			// Assign network admin rights.
-			key: root_key,
+			key: root_key.clone(),
+		}),
+		pallet_wivsupplychain: Some(WivSupplyChainConfig {
+			// The network admin registers the first assets, other roles are granted through sudo.
+			roles: vec![(root_key, Role::Producer)],
 		}),
 	}
 }
@@ -0,0 +1,222 @@
+//! Confidential asset metadata.
+//!
+//! The metadata of an asset is encrypted with a random key (ChaCha20-Poly1305), and that key is
+//! wrapped for each recipient with an x25519 key agreement. The x25519 key of an account is derived
+//! from its sr25519 key stored in the local keystore under the `wivc` key type, so the node can
+//! decrypt the metadata disclosed to its accounts.
+
+use std::sync::Arc;
+
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce, aead::{Aead, NewAead}};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use node_wivsupplychain_runtime::{
+	opaque::Block, AccountId, Balance, BlockNumber, Moment,
+	pallet_wivsupplychain::{AssetId, EncryptionKey},
+};
+use pallet_wivsupplychain_rpc::WivSupplyChainRuntimeApi;
+use sc_keystore::KeyStorePtr;
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256, Pair, crypto::KeyTypeId, hashing::blake2_256, sr25519};
+use sp_runtime::generic::BlockId;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+/// Key type of the keystore accounts able to decrypt confidential metadata
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"wivc");
+
+/// Length of the nonce preceding the encrypted metadata
+const NONCE_LENGTH: usize = 12;
+
+/// Error code returned when the runtime can't be queried
+const RUNTIME_ERROR: i64 = 1;
+
+/// x25519 secret of a keystore account
+fn x25519_secret(pair: &sr25519::Pair) -> StaticSecret {
+	let mut seed = b"wivsupplychain/x25519".to_vec();
+	seed.extend_from_slice(&pair.to_raw_vec());
+	StaticSecret::from(blake2_256(&seed))
+}
+
+/// Encryption key of a keystore account, to be registered on chain
+pub fn encryption_key(pair: &sr25519::Pair) -> EncryptionKey {
+	PublicKey::from(&x25519_secret(pair)).to_bytes()
+}
+
+/// Key wrapping the metadata key, bound to both public keys of the key agreement
+fn key_encryption_key(shared: &[u8], ephemeral: &PublicKey, recipient: &EncryptionKey) -> [u8; 32] {
+	let mut material = shared.to_vec();
+	material.extend_from_slice(ephemeral.as_bytes());
+	material.extend_from_slice(recipient);
+	blake2_256(&material)
+}
+
+/// Wrap the metadata key for a recipient: ephemeral public key followed by the encrypted key
+pub fn wrap_key(key: &[u8; 32], recipient: &EncryptionKey) -> Vec<u8> {
+	let ephemeral = StaticSecret::from(rand::random::<[u8; 32]>());
+	let ephemeral_public = PublicKey::from(&ephemeral);
+	let shared = ephemeral.diffie_hellman(&PublicKey::from(*recipient));
+	let wrapping_key = key_encryption_key(shared.as_bytes(), &ephemeral_public, recipient);
+	// Each wrapping key is used once, a constant nonce is safe
+	let encrypted = ChaCha20Poly1305::new(Key::from_slice(&wrapping_key))
+		.encrypt(Nonce::from_slice(&[0; NONCE_LENGTH]), &key[..])
+		.expect("encryption of a key can't fail; qed");
+	let mut wrapped = ephemeral_public.as_bytes().to_vec();
+	wrapped.extend_from_slice(&encrypted);
+	wrapped
+}
+
+/// Unwrap the metadata key with a keystore account, none if it hasn't been wrapped for it
+pub fn unwrap_key(pair: &sr25519::Pair, wrapped: &[u8]) -> Option<[u8; 32]> {
+	if wrapped.len() < 32 {
+		return None;
+	}
+	let (ephemeral, encrypted) = wrapped.split_at(32);
+	let mut ephemeral_bytes = [0u8; 32];
+	ephemeral_bytes.copy_from_slice(ephemeral);
+	let ephemeral_public = PublicKey::from(ephemeral_bytes);
+	let secret = x25519_secret(pair);
+	let shared = secret.diffie_hellman(&ephemeral_public);
+	let wrapping_key = key_encryption_key(shared.as_bytes(), &ephemeral_public, &encryption_key(pair));
+	let key = ChaCha20Poly1305::new(Key::from_slice(&wrapping_key))
+		.decrypt(Nonce::from_slice(&[0; NONCE_LENGTH]), encrypted)
+		.ok()?;
+	if key.len() != 32 {
+		return None;
+	}
+	let mut unwrapped = [0u8; 32];
+	unwrapped.copy_from_slice(&key);
+	Some(unwrapped)
+}
+
+/// Encrypt metadata and wrap its key for each recipient, the result is the payload and the
+/// wrapped keys expected by `set_confidential_metadata`
+pub fn seal(metadata: &[u8], recipients: &[EncryptionKey]) -> (Vec<u8>, Vec<Vec<u8>>) {
+	let key = rand::random::<[u8; 32]>();
+	let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+	let encrypted = ChaCha20Poly1305::new(Key::from_slice(&key))
+		.encrypt(Nonce::from_slice(&nonce), metadata)
+		.expect("encryption of the metadata can't fail; qed");
+	let mut payload = nonce.to_vec();
+	payload.extend_from_slice(&encrypted);
+	let wrapped_keys = recipients.iter().map(|recipient| wrap_key(&key, recipient)).collect();
+	(payload, wrapped_keys)
+}
+
+/// Decrypt metadata with a keystore account, none if it hasn't been disclosed to it
+pub fn open(pair: &sr25519::Pair, wrapped_key: &[u8], payload: &[u8]) -> Option<Vec<u8>> {
+	if payload.len() < NONCE_LENGTH {
+		return None;
+	}
+	let key = unwrap_key(pair, wrapped_key)?;
+	let (nonce, encrypted) = payload.split_at(NONCE_LENGTH);
+	ChaCha20Poly1305::new(Key::from_slice(&key))
+		.decrypt(Nonce::from_slice(nonce), encrypted)
+		.ok()
+}
+
+/// Confidential metadata RPC methods
+#[rpc]
+pub trait ConfidentialApi<BlockHash> {
+	/// Accounts of the local keystore able to decrypt metadata, with the encryption key to register
+	#[rpc(name = "wivsupplychain_localEncryptionKeys")]
+	fn local_encryption_keys(&self) -> Result<Vec<(AccountId, H256)>>;
+
+	/// Confidential metadata of an asset, decrypted with a local account it has been disclosed to
+	#[rpc(name = "wivsupplychain_decryptMetadata")]
+	fn decrypt_metadata(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Option<Bytes>>;
+}
+
+/// Implementation of the confidential metadata RPC methods
+pub struct Confidential<C> {
+	client: Arc<C>,
+	keystore: KeyStorePtr,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> Confidential<C> {
+	/// Create a new `Confidential` decrypting with the accounts of the given keystore.
+	pub fn new(client: Arc<C>, keystore: KeyStorePtr, deny_unsafe: DenyUnsafe) -> Self {
+		Confidential { client, keystore, deny_unsafe }
+	}
+
+	/// Accounts of the local keystore able to decrypt metadata
+	fn local_pairs(&self) -> Vec<sr25519::Pair> {
+		let keystore = self.keystore.read();
+		keystore.public_keys_by_type::<sr25519::Public>(KEY_TYPE)
+			.unwrap_or_default()
+			.into_iter()
+			.filter_map(|public| keystore.key_pair_by_type::<sr25519::Pair>(&public, KEY_TYPE).ok())
+			.collect()
+	}
+}
+
+impl<C> ConfidentialApi<<Block as sp_runtime::traits::Block>::Hash> for Confidential<C>
+where
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: WivSupplyChainRuntimeApi<Block, AccountId, Balance, BlockNumber, Moment>,
+{
+	fn local_encryption_keys(&self) -> Result<Vec<(AccountId, H256)>> {
+		Ok(self.local_pairs()
+			.iter()
+			.map(|pair| (AccountId::from(pair.public()), H256::from(encryption_key(pair))))
+			.collect())
+	}
+
+	fn decrypt_metadata(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as sp_runtime::traits::Block>::Hash>,
+	) -> Result<Option<Bytes>> {
+		// Plaintext metadata must not leak to remote callers
+		self.deny_unsafe.check_if_safe()?;
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let confidential = api.confidential_metadata(&at, asset_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(RUNTIME_ERROR),
+			message: "Unable to query the wivsupplychain runtime api.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let confidential = match confidential {
+			Some(confidential) => confidential,
+			None => return Ok(None),
+		};
+		Ok(self.local_pairs().iter().find_map(|pair| {
+			let account = AccountId::from(pair.public());
+			confidential.wrapped_keys.iter()
+				.find(|(recipient, _)| *recipient == account)
+				.and_then(|(_, wrapped_key)| open(pair, wrapped_key, &confidential.payload))
+		}).map(Into::into))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sealed_metadata_is_opened_by_its_recipients_only() {
+		let (alice, _) = sr25519::Pair::generate();
+		let (bob, _) = sr25519::Pair::generate();
+		let (eve, _) = sr25519::Pair::generate();
+		let metadata = b"{\"price\":\"12500 GBP\"}";
+		let (payload, wrapped_keys) = seal(metadata, &[encryption_key(&alice), encryption_key(&bob)]);
+		assert_eq!(open(&alice, &wrapped_keys[0], &payload), Some(metadata.to_vec()));
+		assert_eq!(open(&bob, &wrapped_keys[1], &payload), Some(metadata.to_vec()));
+		// A key is only unwrapped by the account it has been wrapped for.
+		assert_eq!(open(&bob, &wrapped_keys[0], &payload), None);
+		assert_eq!(open(&eve, &wrapped_keys[0], &payload), None);
+		assert!(wrapped_keys.iter().all(|key| key.len() <= node_wivsupplychain_runtime::pallet_wivsupplychain::MAX_WRAPPED_KEY_LENGTH));
+	}
+
+	#[test]
+	fn tampered_payload_is_rejected() {
+		let (alice, _) = sr25519::Pair::generate();
+		let (mut payload, wrapped_keys) = seal(b"{\"owner\":\"Alice\"}", &[encryption_key(&alice)]);
+		let last = payload.len() - 1;
+		payload[last] ^= 1;
+		assert_eq!(open(&alice, &wrapped_keys[0], &payload), None);
+		assert_eq!(open(&alice, &[], &payload), None);
+	}
+}
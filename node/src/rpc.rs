@@ -7,44 +7,56 @@
 
 use std::sync::Arc;
 
-use node_wivsupplychain_runtime::{opaque::Block, AccountId, Balance, Index};
+use node_wivsupplychain_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Index, Moment};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::{Error as BlockChainError, HeaderMetadata, HeaderBackend};
 use sp_block_builder::BlockBuilder;
+use sp_core::offchain::OffchainStorage;
+use sc_keystore::KeyStorePtr;
 pub use sc_rpc_api::DenyUnsafe;
 use sp_transaction_pool::TransactionPool;
 
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Offchain storage of the node, holding the documents anchored on chain
+	pub offchain_storage: Option<S>,
+	/// Keystore of the node, holding the accounts decrypting confidential metadata
+	pub keystore: KeyStorePtr,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, S>(
+	deps: FullDeps<C, P, S>,
 ) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_wivsupplychain_rpc::WivSupplyChainRuntimeApi<Block, AccountId, Balance, BlockNumber, Moment>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
+	S: OffchainStorage + 'static,
 {
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
+	use pallet_wivsupplychain_rpc::{WivSupplyChain, WivSupplyChainApi, Documents, DocumentsApi};
+	use crate::confidential::{Confidential, ConfidentialApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
 	let FullDeps {
 		client,
 		pool,
 		deny_unsafe,
+		offchain_storage,
+		keystore,
 	} = deps;
 
 	io.extend_with(
@@ -55,10 +67,19 @@ pub fn create_full<C, P>(
 		TransactionPaymentApi::to_delegate(TransactionPayment::new(client.clone()))
 	);
 
-	// Extend this RPC with a custom API by using the following syntax.
-	// `YourRpcStruct` should have a reference to a client, which is needed
-	// to call into the runtime.
-	// `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
+	io.extend_with(
+		WivSupplyChainApi::to_delegate(WivSupplyChain::new(client.clone()))
+	);
+
+	io.extend_with(
+		ConfidentialApi::to_delegate(Confidential::new(client.clone(), keystore, deny_unsafe))
+	);
+
+	if let Some(storage) = offchain_storage {
+		io.extend_with(
+			DocumentsApi::to_delegate(Documents::new(storage, deny_unsafe))
+		);
+	}
 
 	io
 }
@@ -0,0 +1,65 @@
+//! Runtime API definition for the Wiv Supply Chain Pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::Codec;
+use pallet_wivsupplychain::{
+	AssetId, AssetDetails, AssetStatus, AssetTree, Attestation, Auction, ConfidentialMetadata, EncryptionKey,
+	FreezeReason, Policy, PolicyId, ProvenanceEntry,
+};
+
+sp_api::decl_runtime_apis! {
+	/// Queries on the assets stored by the Wiv Supply Chain Pallet
+	pub trait WivSupplyChainApi<AccountId, Balance, BlockNumber, Moment> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// Details of an asset
+		fn asset(asset_id: AssetId) -> Option<AssetDetails<AccountId, Balance>>;
+		/// Ids of the assets owned by an account
+		fn assets_by_owner(owner: AccountId) -> Vec<AssetId>;
+		/// Ids of the assets in custody of an account
+		fn assets_by_custodian(custodian: AccountId) -> Vec<AssetId>;
+		/// Page of the provenance log of an asset, oldest entries first, the runtime caps `page_size`
+		fn history(
+			asset_id: AssetId,
+			page: u32,
+			page_size: u32,
+		) -> Vec<ProvenanceEntry<AccountId, BlockNumber, Moment>>;
+		/// Page of the condition reports of an asset, oldest first, the runtime caps `page_size`
+		fn attestations(
+			asset_id: AssetId,
+			page: u32,
+			page_size: u32,
+		) -> Vec<Attestation<AccountId, BlockNumber, Moment>>;
+		/// Latest condition report of each kind
+		fn latest_attestations(asset_id: AssetId) -> Vec<Attestation<AccountId, BlockNumber, Moment>>;
+		/// Containment tree of an asset
+		fn tree(asset_id: AssetId) -> Option<AssetTree>;
+		/// Assets in which an asset is packed, from its direct parent to the top-level asset
+		fn ancestors(asset_id: AssetId) -> Vec<AssetId>;
+		/// Assets from which an asset has been split or merged, directly or not
+		fn lineage(asset_id: AssetId) -> Vec<AssetId>;
+		/// Check that a document is the one of an asset, either anchored or stored on chain
+		fn verify_asset_document(asset_id: AssetId, document: Vec<u8>) -> bool;
+		/// Encrypted metadata of an asset, with its key wrapped for each recipient
+		fn confidential_metadata(asset_id: AssetId) -> Option<ConfidentialMetadata<AccountId, Balance>>;
+		/// Encryption key registered by an account
+		fn encryption_key(account: AccountId) -> Option<EncryptionKey>;
+		/// Insurance policy
+		fn policy(policy_id: PolicyId) -> Option<Policy<AccountId, Balance, BlockNumber>>;
+		/// Policies covering an asset, until they expire
+		fn asset_policies(asset_id: AssetId) -> Vec<PolicyId>;
+		/// Running auction of an asset
+		fn auction(asset_id: AssetId) -> Option<Auction<AccountId, Balance, BlockNumber>>;
+		/// Holders of the shares of a fractionalised asset, with the number of shares they hold
+		fn share_holders(asset_id: AssetId) -> Vec<(AccountId, u64)>;
+		/// Lifecycle status of an asset, including removed assets
+		fn asset_status(asset_id: AssetId) -> Option<AssetStatus>;
+		/// Reason an asset has been frozen, none if it isn't frozen
+		fn freeze_reason(asset_id: AssetId) -> Option<FreezeReason>;
+	}
+}
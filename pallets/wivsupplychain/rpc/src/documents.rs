@@ -0,0 +1,81 @@
+//! Node-local store of the documents anchored on chain by their hash.
+//! Documents are kept in the persistent offchain storage of the node, where offchain workers can read them.
+
+use std::sync::Arc;
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use parking_lot::RwLock;
+use sc_rpc_api::DenyUnsafe;
+use sp_core::{Bytes, H256, hashing::blake2_256, offchain::OffchainStorage};
+
+/// Prefix of the keys of the documents in the offchain storage
+pub const DOCUMENT_KEY_PREFIX: &[u8] = b"wivsupplychain::document::";
+
+/// Key of a document in the offchain storage
+pub fn document_key(digest: &H256) -> Vec<u8> {
+	let mut key = DOCUMENT_KEY_PREFIX.to_vec();
+	key.extend_from_slice(digest.as_bytes());
+	key
+}
+
+/// Document store RPC methods
+#[rpc]
+pub trait DocumentsApi {
+	/// Store a document on this node and return its blake2-256 hash, to be anchored on chain
+	#[rpc(name = "wivsupplychain_storeDocument")]
+	fn store_document(&self, document: Bytes) -> Result<H256>;
+
+	/// Document stored on this node with the given hash
+	#[rpc(name = "wivsupplychain_getDocument")]
+	fn get_document(&self, digest: H256) -> Result<Option<Bytes>>;
+}
+
+/// Implementation of the document store RPC methods
+pub struct Documents<S> {
+	storage: Arc<RwLock<S>>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<S: OffchainStorage> Documents<S> {
+	/// Create a new `Documents` on top of the offchain storage of the node.
+	pub fn new(storage: S, deny_unsafe: DenyUnsafe) -> Self {
+		Documents { storage: Arc::new(RwLock::new(storage)), deny_unsafe }
+	}
+}
+
+impl<S: OffchainStorage + 'static> DocumentsApi for Documents<S> {
+	fn store_document(&self, document: Bytes) -> Result<H256> {
+		// Anyone could fill the disk of the node otherwise
+		self.deny_unsafe.check_if_safe()?;
+		let digest = H256::from(blake2_256(&document));
+		self.storage.write().set(sp_offchain::STORAGE_PREFIX, &document_key(&digest), &document);
+		Ok(digest)
+	}
+
+	fn get_document(&self, digest: H256) -> Result<Option<Bytes>> {
+		Ok(self.storage.read().get(sp_offchain::STORAGE_PREFIX, &document_key(&digest)).map(Into::into))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::offchain::storage::InMemOffchainStorage;
+
+	#[test]
+	fn stored_documents_are_found_by_their_hash() {
+		let documents = Documents::new(InMemOffchainStorage::default(), DenyUnsafe::No);
+		let document = Bytes(b"{\"producer\":\"Krug\"}".to_vec());
+		let digest = documents.store_document(document.clone()).unwrap();
+		assert_eq!(digest, H256::from(blake2_256(&document)));
+		assert_eq!(documents.get_document(digest).unwrap(), Some(document));
+		assert_eq!(documents.get_document(H256::zero()).unwrap(), None);
+	}
+
+	#[test]
+	fn storing_documents_is_unsafe() {
+		let documents = Documents::new(InMemOffchainStorage::default(), DenyUnsafe::Yes);
+		assert!(documents.store_document(Bytes(b"{}".to_vec())).is_err());
+	}
+}
@@ -0,0 +1,396 @@
+//! RPC interface for the Wiv Supply Chain Pallet.
+//! Assets are returned as JSON, with their data decoded, instead of SCALE encoded bytes.
+
+use std::sync::Arc;
+use std::marker::PhantomData;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Serialize, Deserialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use pallet_wivsupplychain::{
+	AssetId, AssetDetails, AssetMetadata, AssetStatus, AssetTree, Attestation, Auction, FreezeReason, Policy,
+	PolicyId, ProvenanceEntry,
+};
+
+pub use pallet_wivsupplychain_rpc_runtime_api::WivSupplyChainApi as WivSupplyChainRuntimeApi;
+
+mod documents;
+pub use documents::{Documents, DocumentsApi};
+
+/// Error code returned when the runtime can't be queried
+const RUNTIME_ERROR: i64 = 1;
+/// Largest page of the history or attestations of an asset returned at once
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Metadata of an asset, with its text fields decoded
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+	pub producer: String,
+	pub vintage: Option<u16>,
+	pub format: String,
+	pub lot: String,
+	pub location: String,
+	pub quantity: u64,
+}
+
+impl From<AssetMetadata> for Metadata {
+	fn from(metadata: AssetMetadata) -> Self {
+		Metadata {
+			producer: String::from_utf8_lossy(&metadata.producer).into_owned(),
+			vintage: metadata.vintage,
+			format: String::from_utf8_lossy(&metadata.format).into_owned(),
+			lot: String::from_utf8_lossy(&metadata.lot).into_owned(),
+			location: String::from_utf8_lossy(&metadata.location).into_owned(),
+			quantity: metadata.quantity,
+		}
+	}
+}
+
+/// Asset as returned by the RPC
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Asset<AccountId, Balance> {
+	pub id: AssetId,
+	pub owner: AccountId,
+	pub custodian: AccountId,
+	/// json document carried by the asset data (base64 encoding + json), null when kept off chain
+	pub document: serde_json::Value,
+	/// blake2-256 hash of the document kept off chain
+	pub digest: Option<H256>,
+	/// location of the document kept off chain
+	pub uri: Option<String>,
+	pub metadata: Metadata,
+	pub deposit: Balance,
+	pub status: AssetStatus,
+}
+
+impl<AccountId, Balance> Asset<AccountId, Balance> {
+	fn new(id: AssetId, details: AssetDetails<AccountId, Balance>) -> Self {
+		let document = base64::decode(&details.data).ok()
+			.and_then(|json| serde_json::from_slice(&json).ok())
+			.unwrap_or(serde_json::Value::Null);
+		Asset {
+			id,
+			owner: details.owner,
+			custodian: details.custodian,
+			document,
+			digest: details.anchor.as_ref().map(|anchor| anchor.digest),
+			uri: details.anchor.map(|anchor| String::from_utf8_lossy(&anchor.uri).into_owned()),
+			metadata: details.metadata.into(),
+			deposit: details.deposit,
+			status: details.status,
+		}
+	}
+}
+
+/// Wiv Supply Chain RPC methods
+#[rpc]
+pub trait WivSupplyChainApi<BlockHash, AccountId, Balance, BlockNumber, Moment> {
+	/// Details of an asset
+	#[rpc(name = "wivsupplychain_getAsset")]
+	fn get_asset(
+		&self,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Asset<AccountId, Balance>>>;
+
+	/// Ids of the assets owned by an account
+	#[rpc(name = "wivsupplychain_getAssetsByOwner")]
+	fn get_assets_by_owner(&self, owner: AccountId, at: Option<BlockHash>) -> Result<Vec<AssetId>>;
+
+	/// Ids of the assets in custody of an account
+	#[rpc(name = "wivsupplychain_getAssetsByCustodian")]
+	fn get_assets_by_custodian(
+		&self,
+		custodian: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<AssetId>>;
+
+	/// Page of the provenance log of an asset, oldest entries first, at most `MAX_PAGE_SIZE` entries
+	#[rpc(name = "wivsupplychain_getHistory")]
+	fn get_history(
+		&self,
+		asset_id: AssetId,
+		page: u32,
+		page_size: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ProvenanceEntry<AccountId, BlockNumber, Moment>>>;
+
+	/// Page of the condition reports of an asset, oldest first, at most `MAX_PAGE_SIZE` reports
+	#[rpc(name = "wivsupplychain_getAttestations")]
+	fn get_attestations(
+		&self,
+		asset_id: AssetId,
+		page: u32,
+		page_size: u32,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Attestation<AccountId, BlockNumber, Moment>>>;
+
+	/// Latest condition report of each kind
+	#[rpc(name = "wivsupplychain_getLatestAttestations")]
+	fn get_latest_attestations(
+		&self,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<Attestation<AccountId, BlockNumber, Moment>>>;
+
+	/// Containment tree of an asset
+	#[rpc(name = "wivsupplychain_getTree")]
+	fn get_tree(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Option<AssetTree>>;
+
+	/// Assets in which an asset is packed, from its direct parent to the top-level asset
+	#[rpc(name = "wivsupplychain_getAncestors")]
+	fn get_ancestors(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Vec<AssetId>>;
+
+	/// Assets from which an asset has been split or merged, directly or not
+	#[rpc(name = "wivsupplychain_getLineage")]
+	fn get_lineage(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Vec<AssetId>>;
+
+	/// Check that a document is the one of an asset, either anchored or stored on chain
+	#[rpc(name = "wivsupplychain_verifyAssetDocument")]
+	fn verify_asset_document(
+		&self,
+		asset_id: AssetId,
+		document: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<bool>;
+
+	/// Insurance policy
+	#[rpc(name = "wivsupplychain_getPolicy")]
+	fn get_policy(
+		&self,
+		policy_id: PolicyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Policy<AccountId, Balance, BlockNumber>>>;
+
+	/// Policies covering an asset, until they expire
+	#[rpc(name = "wivsupplychain_getAssetPolicies")]
+	fn get_asset_policies(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Vec<PolicyId>>;
+
+	/// Running auction of an asset
+	#[rpc(name = "wivsupplychain_getAuction")]
+	fn get_auction(
+		&self,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> Result<Option<Auction<AccountId, Balance, BlockNumber>>>;
+
+	/// Holders of the shares of a fractionalised asset, with the number of shares they hold
+	#[rpc(name = "wivsupplychain_getShareHolders")]
+	fn get_share_holders(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Vec<(AccountId, u64)>>;
+
+	/// Lifecycle status of an asset, including removed assets
+	#[rpc(name = "wivsupplychain_getAssetStatus")]
+	fn get_asset_status(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Option<AssetStatus>>;
+
+	/// Reason an asset has been frozen, null if it isn't frozen
+	#[rpc(name = "wivsupplychain_getFreezeReason")]
+	fn get_freeze_reason(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<Option<FreezeReason>>;
+}
+
+/// Implementation of the Wiv Supply Chain RPC methods
+pub struct WivSupplyChain<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> WivSupplyChain<C, B> {
+	/// Create a new `WivSupplyChain` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		WivSupplyChain { client, _marker: Default::default() }
+	}
+}
+
+/// Convert a runtime api error into a RPC error
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Unable to query the wivsupplychain runtime api.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber, Moment>
+	WivSupplyChainApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber, Moment>
+	for WivSupplyChain<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: WivSupplyChainRuntimeApi<Block, AccountId, Balance, BlockNumber, Moment>,
+	AccountId: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+	Moment: Codec,
+{
+	fn get_asset(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Asset<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let details = api.asset(&at, asset_id).map_err(runtime_error)?;
+		Ok(details.map(|details| Asset::new(asset_id, details)))
+	}
+
+	fn get_assets_by_owner(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AssetId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.assets_by_owner(&at, owner).map_err(runtime_error)
+	}
+
+	fn get_assets_by_custodian(
+		&self,
+		custodian: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AssetId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.assets_by_custodian(&at, custodian).map_err(runtime_error)
+	}
+
+	fn get_history(
+		&self,
+		asset_id: AssetId,
+		page: u32,
+		page_size: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProvenanceEntry<AccountId, BlockNumber, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.history(&at, asset_id, page, page_size.min(MAX_PAGE_SIZE)).map_err(runtime_error)
+	}
+
+	fn get_attestations(
+		&self,
+		asset_id: AssetId,
+		page: u32,
+		page_size: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Attestation<AccountId, BlockNumber, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.attestations(&at, asset_id, page, page_size.min(MAX_PAGE_SIZE)).map_err(runtime_error)
+	}
+
+	fn get_latest_attestations(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Attestation<AccountId, BlockNumber, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.latest_attestations(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_tree(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AssetTree>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.tree(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_ancestors(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AssetId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.ancestors(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_lineage(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AssetId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.lineage(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn verify_asset_document(
+		&self,
+		asset_id: AssetId,
+		document: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.verify_asset_document(&at, asset_id, document.to_vec()).map_err(runtime_error)
+	}
+
+	fn get_policy(
+		&self,
+		policy_id: PolicyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Policy<AccountId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.policy(&at, policy_id).map_err(runtime_error)
+	}
+
+	fn get_asset_policies(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<PolicyId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.asset_policies(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_auction(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<Auction<AccountId, Balance, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.auction(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_share_holders(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(AccountId, u64)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.share_holders(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_asset_status(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<AssetStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.asset_status(&at, asset_id).map_err(runtime_error)
+	}
+
+	fn get_freeze_reason(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<FreezeReason>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		api.freeze_reason(&at, asset_id).map_err(runtime_error)
+	}
+}
@@ -0,0 +1,750 @@
+//! Benchmarking setup for pallet-wivsupplychain
+
+use super::*;
+use frame_system::RawOrigin;
+use frame_support::traits::{OnFinalize, OnInitialize};
+use frame_benchmarking::{benchmarks, account, whitelisted_caller};
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+/// Asset data (base64 encoding + json) of at most `len` bytes, `len` must be at least 108
+fn asset_data(len: u32) -> Vec<u8> {
+	lot_data(len, None)
+}
+
+/// Asset data of at most `len` bytes with the given quantity, `len` must be at least 128
+fn lot_data(len: u32, quantity: Option<u32>) -> Vec<u8> {
+	let mut header = b"{\"producer\":\"Wiv\",\"vintage\":2015,\"format\":\"750ml\",\"lot\":\"L1\",".to_vec();
+	if let Some(quantity) = quantity {
+		header.extend_from_slice(b"\"quantity\":");
+		header.extend_from_slice(alloc::format!("{}", quantity).as_bytes());
+		header.push(b',');
+	}
+	header.extend_from_slice(b"\"location\":\"");
+	let footer: &[u8] = b"\"}";
+	// base64 encodes 3 bytes in 4 characters
+	let json_len = (len as usize / 4 * 3).max(header.len() + footer.len());
+	let mut json = header;
+	json.resize(json_len - footer.len(), b'x');
+	json.extend_from_slice(footer);
+	base64::encode(json).into_bytes()
+}
+
+/// Give `who` enough funds to pay any storage deposit
+fn fund<T: Trait>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 4u32.into());
+}
+
+/// Caller of the benchmarked calls, a producer
+fn funded_caller<T: Trait>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	<Roles<T>>::insert(&caller, Role::Producer, true);
+	caller
+}
+
+/// Account with the custodian role
+fn custodian<T: Trait>(index: u32) -> T::AccountId {
+	let who: T::AccountId = account("custodian", index, SEED);
+	<Roles<T>>::insert(&who, Role::Custodian, true);
+	who
+}
+
+/// Account with the inspector role
+fn inspector<T: Trait>() -> T::AccountId {
+	let who: T::AccountId = account("inspector", 0, SEED);
+	<Roles<T>>::insert(&who, Role::Inspector, true);
+	who
+}
+
+/// Funded account with the insurer role
+fn insurer<T: Trait>() -> T::AccountId {
+	let who: T::AccountId = account("insurer", 0, SEED);
+	fund::<T>(&who);
+	<Roles<T>>::insert(&who, Role::Insurer, true);
+	who
+}
+
+/// Issue a policy on the assets of `holder` expiring at `expires` and return its id
+fn issue<T: Trait>(
+	insurer: &T::AccountId,
+	holder: &T::AccountId,
+	assets: Vec<AssetId>,
+	expires: T::BlockNumber,
+) -> Result<PolicyId, &'static str> {
+	let premium = PremiumSchedule { amount: 100u32.into(), period: 10u32.into() };
+	Module::<T>::issue_policy(
+		RawOrigin::Signed(insurer.clone()).into(), holder.clone(), assets, 10_000u32.into(), premium, expires
+	)?;
+	Ok(Module::<T>::next_policy_id() - 1)
+}
+
+/// Funded account bidding in auctions
+fn bidder<T: Trait>(index: u32) -> T::AccountId {
+	let who: T::AccountId = account("bidder", index, SEED);
+	fund::<T>(&who);
+	who
+}
+
+/// Account with a registered encryption key
+fn recipient<T: Trait>(index: u32) -> T::AccountId {
+	let who: T::AccountId = account("recipient", index, SEED);
+	<EncryptionKeys<T>>::insert(&who, [index as u8; 32]);
+	who
+}
+
+/// Register an asset of `len` bytes for `owner` and return its id
+fn create_asset<T: Trait>(owner: &T::AccountId, len: u32) -> Result<AssetId, &'static str> {
+	Module::<T>::new_asset(RawOrigin::Signed(owner.clone()).into(), asset_data(len))?;
+	Ok(Module::<T>::next_asset_id() - 1)
+}
+
+/// Pack `n` new assets of `owner` below `root`, breadth first
+fn pack_tree<T: Trait>(owner: &T::AccountId, root: AssetId, n: u32) -> Result<(), &'static str> {
+	let mut nodes = vec![root];
+	for index in 0 .. n as usize {
+		let child = create_asset::<T>(owner, 128)?;
+		let parent = nodes[index / T::MaxChildren::get() as usize];
+		Module::<T>::pack(RawOrigin::Signed(owner.clone()).into(), parent, vec![child])?;
+		nodes.push(child);
+	}
+	Ok(())
+}
+
+benchmarks! {
+	_ { }
+
+	new_asset {
+		let b in 128 .. T::MaxAssetLength::get();
+		let caller = funded_caller::<T>();
+		let asset = asset_data(b);
+	}: _(RawOrigin::Signed(caller.clone()), asset)
+	verify {
+		assert_eq!(Module::<T>::asset(0).map(|a| a.owner), Some(caller));
+	}
+
+	new_anchored_asset {
+		let u in 1 .. T::MaxUriLength::get();
+		let caller = funded_caller::<T>();
+		let uri = vec![b'x'; u as usize];
+	}: _(RawOrigin::Signed(caller.clone()), H256::repeat_byte(1), uri)
+	verify {
+		assert_eq!(Module::<T>::asset(0).map(|a| a.owner), Some(caller));
+	}
+
+	new_assets_batch {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let assets = (0 .. n)
+			.map(|_| AssetInput { data: asset_data(T::MaxAssetLength::get()) })
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller.clone()), assets)
+	verify {
+		assert_eq!(Module::<T>::assets_of(&caller).len(), n as usize);
+	}
+
+	transfer_asset {
+		let n in 0 .. Module::<T>::max_descendants();
+		let b in 128 .. T::MaxAssetLength::get();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, b)?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		fund::<T>(&recipient);
+	}: _(RawOrigin::Signed(caller), asset_id, recipient.clone())
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.owner), Some(recipient));
+	}
+
+	list_for_sale {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, 1_000u32.into())
+	verify {
+		assert_eq!(Module::<T>::listing(asset_id).map(|l| l.seller), Some(caller));
+	}
+
+	delist {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		Module::<T>::list_for_sale(RawOrigin::Signed(caller.clone()).into(), asset_id, 1_000u32.into())?;
+	}: _(RawOrigin::Signed(caller), asset_id)
+	verify {
+		assert!(Module::<T>::listing(asset_id).is_none());
+	}
+
+	buy {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		// The seller is not the producer, the royalty is paid to another account
+		let seller: T::AccountId = account("seller", 0, SEED);
+		fund::<T>(&seller);
+		Module::<T>::transfer_asset(RawOrigin::Signed(caller).into(), asset_id, seller.clone())?;
+		Module::<T>::list_for_sale(RawOrigin::Signed(seller).into(), asset_id, 1_000u32.into())?;
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		fund::<T>(&buyer);
+	}: _(RawOrigin::Signed(buyer.clone()), asset_id)
+
+	start_auction {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+	}: _(RawOrigin::Signed(caller), asset_id, AuctionKind::SealedBid, 1_000u32.into(), T::MaxAuctionDuration::get())
+
+	bid {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		Module::<T>::start_auction(
+			RawOrigin::Signed(caller).into(), asset_id, AuctionKind::English, 1_000u32.into(), T::MaxAuctionDuration::get()
+		)?;
+		// The highest bid is outbid and unreserved
+		Module::<T>::bid(RawOrigin::Signed(bidder::<T>(0)).into(), asset_id, 1_000u32.into())?;
+		let bidder = bidder::<T>(1);
+	}: _(RawOrigin::Signed(bidder), asset_id, 2_000u32.into())
+
+	commit_bid {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		Module::<T>::start_auction(
+			RawOrigin::Signed(caller).into(), asset_id, AuctionKind::SealedBid, 1_000u32.into(), T::MaxAuctionDuration::get()
+		)?;
+		let bidder = bidder::<T>(0);
+		let commitment = Module::<T>::bid_commitment(&bidder, 1_000u32.into(), &H256::repeat_byte(1));
+	}: _(RawOrigin::Signed(bidder), asset_id, commitment, 2_000u32.into())
+
+	reveal_bid {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		let duration = T::MaxAuctionDuration::get();
+		Module::<T>::start_auction(
+			RawOrigin::Signed(caller).into(), asset_id, AuctionKind::SealedBid, 1_000u32.into(), duration
+		)?;
+		let salt = H256::repeat_byte(1);
+		let outbid = bidder::<T>(0);
+		let commitment = Module::<T>::bid_commitment(&outbid, 1_000u32.into(), &salt);
+		Module::<T>::commit_bid(RawOrigin::Signed(outbid.clone()).into(), asset_id, commitment, 1_000u32.into())?;
+		let bidder = bidder::<T>(1);
+		let commitment = Module::<T>::bid_commitment(&bidder, 2_000u32.into(), &salt);
+		Module::<T>::commit_bid(RawOrigin::Signed(bidder.clone()).into(), asset_id, commitment, 2_000u32.into())?;
+		frame_system::Module::<T>::set_block_number(frame_system::Module::<T>::block_number() + duration);
+		// The revealed bid outbids the highest bid revealed so far
+		Module::<T>::reveal_bid(RawOrigin::Signed(outbid).into(), asset_id, 1_000u32.into(), salt)?;
+	}: _(RawOrigin::Signed(bidder), asset_id, 2_000u32.into(), salt)
+
+	cancel_auction {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		let duration = T::MaxAuctionDuration::get();
+		Module::<T>::start_auction(
+			RawOrigin::Signed(caller.clone()).into(), asset_id, AuctionKind::English, 1_000u32.into(), duration
+		)?;
+	}: _(RawOrigin::Signed(caller), asset_id)
+
+	fractionalise {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+	}: _(RawOrigin::Signed(caller), asset_id, 1_000)
+
+	transfer_shares {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		Module::<T>::fractionalise(RawOrigin::Signed(caller.clone()).into(), asset_id, 1_000)?;
+		// The receiver already holds shares
+		let receiver: T::AccountId = account("receiver", 0, SEED);
+		Module::<T>::transfer_shares(RawOrigin::Signed(caller.clone()).into(), asset_id, receiver.clone(), 1)?;
+	}: _(RawOrigin::Signed(caller), asset_id, receiver, 100)
+
+	redeem {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		Module::<T>::fractionalise(RawOrigin::Signed(caller.clone()).into(), asset_id, 1_000)?;
+		// The shares are redeemed by another account, the assets change hands
+		let holder: T::AccountId = account("holder", 0, SEED);
+		fund::<T>(&holder);
+		Module::<T>::transfer_shares(RawOrigin::Signed(caller).into(), asset_id, holder.clone(), 1_000)?;
+	}: _(RawOrigin::Signed(holder), asset_id)
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.owner), Some(buyer));
+	}
+
+	remove_asset {
+		let b in 128 .. T::MaxAssetLength::get();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, b)?;
+	}: _(RawOrigin::Signed(caller), asset_id, RemovalReason::Consumed)
+	verify {
+		assert!(Module::<T>::asset(asset_id).is_none());
+	}
+
+	split_asset {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		Module::<T>::new_asset(RawOrigin::Signed(caller.clone()).into(), lot_data(T::MaxAssetLength::get(), Some(n)))?;
+		let asset_id = Module::<T>::next_asset_id() - 1;
+		let outputs = (0 .. n)
+			.map(|_| AssetInput { data: lot_data(T::MaxAssetLength::get(), Some(1)) })
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller.clone()), asset_id, outputs)
+	verify {
+		assert_eq!(Module::<T>::assets_of(&caller).len(), n as usize);
+	}
+
+	merge_assets {
+		let n in 1 .. T::MaxBatchSize::get();
+		let b in 128 .. T::MaxAssetLength::get();
+		let caller = funded_caller::<T>();
+		let mut sources = Vec::new();
+		for _ in 0 .. n {
+			sources.push(create_asset::<T>(&caller, 128)?);
+		}
+		let data = lot_data(b, Some(n));
+	}: _(RawOrigin::Signed(caller.clone()), sources, data)
+	verify {
+		assert_eq!(Module::<T>::assets_of(&caller).len(), 1);
+	}
+
+	transfer_custody {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		// The tolerance set for the current custodian is cleared
+		let tolerance = Some(EnvironmentTolerance::default());
+		Module::<T>::set_tolerance(RawOrigin::Signed(caller.clone()).into(), asset_id, tolerance)?;
+		let receiver = custodian::<T>(0);
+	}: _(RawOrigin::Signed(caller), asset_id, receiver.clone())
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.custodian), Some(receiver));
+	}
+
+	offer_transfer {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let receiver = custodian::<T>(0);
+	}: _(RawOrigin::Signed(caller), asset_id, receiver.clone(), Some(AttestationKind::Ullage))
+	verify {
+		assert_eq!(Module::<T>::offer(asset_id).map(|o| o.to), Some(receiver));
+	}
+
+	accept_transfer {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		let receiver = custodian::<T>(0);
+		// The receiver requires a recent attestation
+		Module::<T>::attest(
+			RawOrigin::Signed(inspector::<T>()).into(), asset_id, AttestationKind::Ullage, H256::repeat_byte(1), MAX_SCORE
+		)?;
+		let attestation = Some(AttestationKind::Ullage);
+		Module::<T>::offer_transfer(RawOrigin::Signed(caller).into(), asset_id, receiver.clone(), attestation)?;
+	}: _(RawOrigin::Signed(receiver.clone()), asset_id)
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.custodian), Some(receiver));
+	}
+
+	reject_transfer {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let receiver = custodian::<T>(0);
+		Module::<T>::offer_transfer(RawOrigin::Signed(caller).into(), asset_id, receiver.clone(), None)?;
+	}: _(RawOrigin::Signed(receiver), asset_id)
+	verify {
+		assert!(Module::<T>::offer(asset_id).is_none());
+	}
+
+	cancel_offer {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let receiver = custodian::<T>(0);
+		Module::<T>::offer_transfer(RawOrigin::Signed(caller.clone()).into(), asset_id, receiver, None)?;
+	}: _(RawOrigin::Signed(caller), asset_id)
+	verify {
+		assert!(Module::<T>::offer(asset_id).is_none());
+	}
+
+	report_lost {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		// The pending offer is cancelled
+		Module::<T>::offer_transfer(RawOrigin::Signed(caller.clone()).into(), asset_id, custodian::<T>(0), None)?;
+	}: _(RawOrigin::Signed(caller), asset_id)
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.status), Some(AssetStatus::Lost));
+	}
+
+	report_found {
+		let n in 0 .. Module::<T>::max_descendants();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, asset_id, n)?;
+		// The asset is lost by its custodian and found by its owner
+		let custodian = custodian::<T>(0);
+		Module::<T>::transfer_custody(RawOrigin::Signed(caller.clone()).into(), asset_id, custodian.clone())?;
+		Module::<T>::report_lost(RawOrigin::Signed(custodian).into(), asset_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert_eq!(Module::<T>::asset(asset_id).map(|a| a.custodian), Some(caller));
+	}
+
+	attest {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let inspector = inspector::<T>();
+	}: _(RawOrigin::Signed(inspector), asset_id, AttestationKind::Ullage, H256::repeat_byte(1), MAX_SCORE)
+	verify {
+		assert_eq!(Module::<T>::attestation_count(asset_id), 1);
+	}
+
+	set_tolerance {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+	}: _(RawOrigin::Signed(caller), asset_id, Some(EnvironmentTolerance::default()))
+	verify {
+		assert!(Module::<T>::tolerance(asset_id).is_some());
+	}
+
+	submit_environment_unsigned {
+		let r in 1 .. T::MaxReadings::get();
+		let a in 0 .. T::MaxMonitoredAssets::get();
+		let caller = funded_caller::<T>();
+		let location = Module::<T>::decode_metadata(&asset_data(128))?.location;
+		// The signature is checked by `validate_unsigned`, not by the call
+		let public = T::Public::decode(&mut &[0u8; 128][..]).map_err(|_| "can't decode a public key")?;
+		let signature = T::Signature::decode(&mut &[0u8; 128][..]).map_err(|_| "can't decode a signature")?;
+		let reporter = public.clone().into_account();
+		<Roles<T>>::insert(&reporter, Role::Custodian, true);
+		// The monitored assets are in the custody of the reporter
+		for _ in 0 .. a {
+			let asset_id = create_asset::<T>(&caller, 128)?;
+			Module::<T>::transfer_custody(RawOrigin::Signed(caller.clone()).into(), asset_id, reporter.clone())?;
+			let tolerance = Some(EnvironmentTolerance::default());
+			Module::<T>::set_tolerance(RawOrigin::Signed(caller.clone()).into(), asset_id, tolerance)?;
+		}
+		// The first location holds the monitored assets, every one of them is breached
+		let readings = (0 .. r).map(|index| EnvironmentReading {
+			location: if index == 0 { location.clone() } else { index.encode() },
+			min_temperature: 100,
+			max_temperature: 100,
+			min_humidity: 700,
+			max_humidity: 700,
+			samples: 1,
+		}).collect();
+		let block_number = <frame_system::Module<T>>::block_number();
+		let payload = TelemetryPayload { block_number, readings, public };
+	}: _(RawOrigin::None, payload, signature)
+	verify {
+		assert_eq!(Module::<T>::last_telemetry(&reporter), Some(block_number));
+	}
+
+	register_encryption_key {
+		let caller: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller.clone()), [1u8; 32])
+	verify {
+		assert_eq!(Module::<T>::encryption_key(&caller), Some([1u8; 32]));
+	}
+
+	set_confidential_metadata {
+		let b in 1 .. T::MaxAssetLength::get();
+		let r in 0 .. T::MaxRecipients::get();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let wrapped_keys = (0 .. r).map(|index| (recipient::<T>(index), vec![0u8; MAX_WRAPPED_KEY_LENGTH])).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), asset_id, vec![0u8; b as usize], wrapped_keys)
+	verify {
+		assert_eq!(Module::<T>::confidential(asset_id).map(|c| c.wrapped_keys.len()), Some(r as usize));
+	}
+
+	grant_access {
+		let r in 0 .. T::MaxRecipients::get() - 1;
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let wrapped_keys = (0 .. r).map(|index| (recipient::<T>(index), vec![0u8; MAX_WRAPPED_KEY_LENGTH])).collect::<Vec<_>>();
+		let payload = vec![0u8; T::MaxAssetLength::get() as usize];
+		Module::<T>::set_confidential_metadata(RawOrigin::Signed(caller.clone()).into(), asset_id, payload, wrapped_keys)?;
+		let account = recipient::<T>(r);
+	}: _(RawOrigin::Signed(caller), asset_id, account, vec![0u8; MAX_WRAPPED_KEY_LENGTH])
+	verify {
+		assert_eq!(Module::<T>::confidential(asset_id).map(|c| c.wrapped_keys.len()), Some(r as usize + 1));
+	}
+
+	revoke_access {
+		let r in 1 .. T::MaxRecipients::get();
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let wrapped_keys = (0 .. r).map(|index| (recipient::<T>(index), vec![0u8; MAX_WRAPPED_KEY_LENGTH])).collect::<Vec<_>>();
+		let payload = vec![0u8; T::MaxAssetLength::get() as usize];
+		Module::<T>::set_confidential_metadata(RawOrigin::Signed(caller.clone()).into(), asset_id, payload, wrapped_keys)?;
+		// The last recipient is the most expensive to find
+		let account = recipient::<T>(r - 1);
+	}: _(RawOrigin::Signed(caller), asset_id, account)
+	verify {
+		assert_eq!(Module::<T>::confidential(asset_id).map(|c| c.wrapped_keys.len()), Some(r as usize - 1));
+	}
+
+	pack {
+		let n in 1 .. T::MaxChildren::get();
+		let caller = funded_caller::<T>();
+		let parent = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let mut children = Vec::new();
+		for _ in 0 .. n {
+			children.push(create_asset::<T>(&caller, 128)?);
+		}
+	}: _(RawOrigin::Signed(caller), parent, children)
+	verify {
+		assert_eq!(Module::<T>::children_of(parent).len(), n as usize);
+	}
+
+	unpack {
+		let n in 1 .. T::MaxChildren::get();
+		let caller = funded_caller::<T>();
+		let parent = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		pack_tree::<T>(&caller, parent, n)?;
+	}: _(RawOrigin::Signed(caller), parent)
+	verify {
+		assert!(Module::<T>::children_of(parent).is_empty());
+	}
+
+	issue_policy {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let mut assets = Vec::new();
+		for _ in 0 .. n {
+			assets.push(create_asset::<T>(&caller, 128)?);
+		}
+		let insurer = insurer::<T>();
+		let premium = PremiumSchedule { amount: 100u32.into(), period: 10u32.into() };
+		let expires = frame_system::Module::<T>::block_number() + 100u32.into();
+	}: _(RawOrigin::Signed(insurer), caller.clone(), assets, 10_000u32.into(), premium, expires)
+	verify {
+		assert_eq!(Module::<T>::policy(0).map(|p| p.holder), Some(caller));
+	}
+
+	pay_premium {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		let policy_id = issue::<T>(&insurer::<T>(), &caller, vec![asset_id], 100u32.into())?;
+	}: _(RawOrigin::Signed(caller), policy_id)
+	verify {
+		assert!(Module::<T>::policy(policy_id).map(|p| p.paid_until) > Some(frame_system::Module::<T>::block_number()));
+	}
+
+	reinstate_policy {
+		let n in 1 .. T::MaxBatchSize::get();
+		let caller = funded_caller::<T>();
+		let mut assets = Vec::new();
+		for _ in 0 .. n {
+			assets.push(create_asset::<T>(&caller, 128)?);
+		}
+		let insurer = insurer::<T>();
+		let policy_id = issue::<T>(&insurer, &caller, assets.clone(), 100u32.into())?;
+		// The assets have been sold, the policy is reinstated for the buyer
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		fund::<T>(&buyer);
+		for asset_id in assets {
+			Module::<T>::transfer_asset(RawOrigin::Signed(caller.clone()).into(), asset_id, buyer.clone())?;
+		}
+	}: _(RawOrigin::Signed(insurer), policy_id)
+	verify {
+		assert_eq!(Module::<T>::policy(policy_id).map(|p| p.holder), Some(buyer));
+	}
+
+	file_claim {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		let policy_id = issue::<T>(&insurer::<T>(), &caller, vec![asset_id], 100u32.into())?;
+		Module::<T>::pay_premium(RawOrigin::Signed(caller.clone()).into(), policy_id)?;
+	}: _(RawOrigin::Signed(caller), policy_id, 1_000u32.into(), H256::repeat_byte(1))
+	verify {
+		assert!(Module::<T>::claim(policy_id).is_some());
+	}
+
+	settle_claim {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, 128)?;
+		let insurer = insurer::<T>();
+		let policy_id = issue::<T>(&insurer, &caller, vec![asset_id], 100u32.into())?;
+		Module::<T>::pay_premium(RawOrigin::Signed(caller.clone()).into(), policy_id)?;
+		Module::<T>::file_claim(RawOrigin::Signed(caller).into(), policy_id, 1_000u32.into(), H256::repeat_byte(1))?;
+	}: _(RawOrigin::Signed(insurer), policy_id, 1_000u32.into())
+	verify {
+		assert!(Module::<T>::claim(policy_id).is_none());
+	}
+
+	on_initialize {
+		let n in 0 .. 100;
+		let p in 0 .. T::MaxPolicyExpiries::get();
+		let caller = funded_caller::<T>();
+		let receiver = custodian::<T>(0);
+		for _ in 0 .. n {
+			let asset_id = create_asset::<T>(&caller, 128)?;
+			Module::<T>::offer_transfer(RawOrigin::Signed(caller.clone()).into(), asset_id, receiver.clone(), None)?;
+		}
+		// Offers and policies expire in the same block
+		let expires = frame_system::Module::<T>::block_number() + T::OfferExpiry::get();
+		// Each policy covers as many assets as possible and has a pending claim
+		let insurer = insurer::<T>();
+		for _ in 0 .. p {
+			let mut assets = Vec::new();
+			for _ in 0 .. T::MaxBatchSize::get() {
+				assets.push(create_asset::<T>(&caller, 128)?);
+			}
+			let policy_id = issue::<T>(&insurer, &caller, assets, expires)?;
+			Module::<T>::pay_premium(RawOrigin::Signed(caller.clone()).into(), policy_id)?;
+			Module::<T>::file_claim(
+				RawOrigin::Signed(caller.clone()).into(), policy_id, 1_000u32.into(), H256::repeat_byte(1)
+			)?;
+		}
+	}: { Module::<T>::on_initialize(expires); }
+
+	on_finalize {
+		let a in 0 .. T::MaxAuctionsPerBlock::get();
+		let caller = funded_caller::<T>();
+		let duration = T::MaxAuctionDuration::get();
+		let bidding_end = frame_system::Module::<T>::block_number() + duration;
+		let salt = H256::repeat_byte(1);
+		// Each auction sells as many assets as possible and has as many sealed bids as possible
+		let mut auctions = Vec::new();
+		for _ in 0 .. a {
+			let asset_id = create_asset::<T>(&caller, 128)?;
+			pack_tree::<T>(&caller, asset_id, Module::<T>::max_descendants())?;
+			Module::<T>::start_auction(
+				RawOrigin::Signed(caller.clone()).into(), asset_id, AuctionKind::SealedBid, 1_000u32.into(), duration
+			)?;
+			for index in 0 .. T::MaxSealedBids::get() {
+				let bidder = bidder::<T>(index);
+				let commitment = Module::<T>::bid_commitment(&bidder, (1_000 + index).into(), &salt);
+				Module::<T>::commit_bid(RawOrigin::Signed(bidder).into(), asset_id, commitment, 2_000u32.into())?;
+			}
+			auctions.push(asset_id);
+		}
+		frame_system::Module::<T>::set_block_number(bidding_end);
+		for &asset_id in auctions.iter() {
+			for index in 0 .. T::MaxSealedBids::get() {
+				let bidder = bidder::<T>(index);
+				Module::<T>::reveal_bid(RawOrigin::Signed(bidder).into(), asset_id, (1_000 + index).into(), salt)?;
+			}
+		}
+	}: { Module::<T>::on_finalize(bidding_end + T::RevealPeriod::get()); }
+	verify {
+		for asset_id in 0 .. n as AssetId {
+			assert!(Module::<T>::offer(asset_id).is_none());
+		}
+		for policy_id in 0 .. p as PolicyId {
+			assert_eq!(Module::<T>::policy(policy_id).map(|p| p.status), Some(PolicyStatus::Expired));
+		}
+	}
+
+	grant_role {
+		let who: T::AccountId = account("who", 0, SEED);
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::grant_role(origin, who.clone(), Role::Inspector)? }
+	verify {
+		assert!(Module::<T>::has_role(&who, Role::Inspector));
+	}
+
+	revoke_role {
+		let who: T::AccountId = account("who", 0, SEED);
+		<Roles<T>>::insert(&who, Role::Inspector, true);
+		let origin = T::AdminOrigin::successful_origin();
+	}: { Module::<T>::revoke_role(origin, who.clone(), Role::Inspector)? }
+	verify {
+		assert!(!Module::<T>::has_role(&who, Role::Inspector));
+	}
+
+	freeze_asset {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		let origin = T::ForceOrigin::successful_origin();
+	}: { Module::<T>::freeze_asset(origin, asset_id, FreezeReason::Seizure)? }
+	verify {
+		assert_eq!(Module::<T>::frozen(asset_id), Some(FreezeReason::Seizure));
+	}
+
+	thaw_asset {
+		let caller = funded_caller::<T>();
+		let asset_id = create_asset::<T>(&caller, T::MaxAssetLength::get())?;
+		Module::<T>::freeze_asset(T::ForceOrigin::successful_origin(), asset_id, FreezeReason::Seizure)?;
+		let origin = T::ForceOrigin::successful_origin();
+	}: { Module::<T>::thaw_asset(origin, asset_id)? }
+	verify {
+		assert!(Module::<T>::frozen(asset_id).is_none());
+	}
+
+	recall {
+		let n in 1 .. T::MaxRecall::get();
+		let caller = funded_caller::<T>();
+		for _ in 0 .. n {
+			create_asset::<T>(&caller, 128)?;
+		}
+		let origin = T::ForceOrigin::successful_origin();
+	}: { Module::<T>::recall(origin, b"Wiv".to_vec(), Some(b"L1".to_vec()), FreezeReason::Recall)? }
+	verify {
+		assert_eq!(Module::<T>::frozen(n as AssetId - 1), Some(FreezeReason::Recall));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_new_asset::<Test>());
+			assert_ok!(test_benchmark_new_anchored_asset::<Test>());
+			assert_ok!(test_benchmark_new_assets_batch::<Test>());
+			assert_ok!(test_benchmark_transfer_asset::<Test>());
+			assert_ok!(test_benchmark_list_for_sale::<Test>());
+			assert_ok!(test_benchmark_delist::<Test>());
+			assert_ok!(test_benchmark_buy::<Test>());
+			assert_ok!(test_benchmark_start_auction::<Test>());
+			assert_ok!(test_benchmark_bid::<Test>());
+			assert_ok!(test_benchmark_commit_bid::<Test>());
+			assert_ok!(test_benchmark_reveal_bid::<Test>());
+			assert_ok!(test_benchmark_cancel_auction::<Test>());
+			assert_ok!(test_benchmark_fractionalise::<Test>());
+			assert_ok!(test_benchmark_transfer_shares::<Test>());
+			assert_ok!(test_benchmark_redeem::<Test>());
+			assert_ok!(test_benchmark_remove_asset::<Test>());
+			assert_ok!(test_benchmark_split_asset::<Test>());
+			assert_ok!(test_benchmark_merge_assets::<Test>());
+			assert_ok!(test_benchmark_transfer_custody::<Test>());
+			assert_ok!(test_benchmark_offer_transfer::<Test>());
+			assert_ok!(test_benchmark_accept_transfer::<Test>());
+			assert_ok!(test_benchmark_reject_transfer::<Test>());
+			assert_ok!(test_benchmark_cancel_offer::<Test>());
+			assert_ok!(test_benchmark_report_lost::<Test>());
+			assert_ok!(test_benchmark_report_found::<Test>());
+			assert_ok!(test_benchmark_attest::<Test>());
+			assert_ok!(test_benchmark_set_tolerance::<Test>());
+			assert_ok!(test_benchmark_submit_environment_unsigned::<Test>());
+			assert_ok!(test_benchmark_register_encryption_key::<Test>());
+			assert_ok!(test_benchmark_set_confidential_metadata::<Test>());
+			assert_ok!(test_benchmark_grant_access::<Test>());
+			assert_ok!(test_benchmark_revoke_access::<Test>());
+			assert_ok!(test_benchmark_pack::<Test>());
+			assert_ok!(test_benchmark_unpack::<Test>());
+			assert_ok!(test_benchmark_issue_policy::<Test>());
+			assert_ok!(test_benchmark_pay_premium::<Test>());
+			assert_ok!(test_benchmark_reinstate_policy::<Test>());
+			assert_ok!(test_benchmark_file_claim::<Test>());
+			assert_ok!(test_benchmark_settle_claim::<Test>());
+			assert_ok!(test_benchmark_on_initialize::<Test>());
+			assert_ok!(test_benchmark_on_finalize::<Test>());
+			assert_ok!(test_benchmark_grant_role::<Test>());
+			assert_ok!(test_benchmark_revoke_role::<Test>());
+			assert_ok!(test_benchmark_freeze_asset::<Test>());
+			assert_ok!(test_benchmark_thaw_asset::<Test>());
+			assert_ok!(test_benchmark_recall::<Test>());
+		});
+	}
+}
@@ -1,50 +1,1129 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+use alloc::string::String;
 use sp_std::prelude::*;
-use frame_support::{decl_module, decl_event, decl_storage, ensure, decl_error,dispatch};
-use frame_system::ensure_signed;
+use sp_std::ops::Range;
+use sp_std::collections::btree_map::BTreeMap;
+use codec::{Encode, Decode};
+use frame_support::{
+	decl_module, decl_event, decl_storage, ensure, decl_error, dispatch, debug, transactional, weights::Weight,
+};
+use frame_support::traits::{
+	Get, Currency, ReservableCurrency, BalanceStatus, EnsureOrigin, ExistenceRequirement, Imbalance, WithdrawReason,
+};
+use frame_support::storage::IterableStorageDoubleMap;
+use frame_system::{ensure_signed, ensure_none};
+use frame_system::offchain::{
+	AppCrypto, SendTransactionTypes, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes,
+};
+use sp_core::{crypto::KeyTypeId, offchain::{Duration, StorageKind}};
+use sp_runtime::{
+	Permill, RuntimeDebug, SaturatedConversion,
+	offchain::http,
+	traits::{BlakeTwo256, Hash as HashT, IdentifyAccount, Saturating, Zero},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
+	},
+};
+use primitive_types::H256;
+use alt_serde::Deserialize;
 
 #[cfg(test)]
 mod mock;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[cfg(test)]
 mod tests;
 
+/// Unique identifier of an asset
+pub type AssetId = u64;
+
+/// Key type of the custodian accounts signing the environment readings
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"wivt");
+
+/// Key of the offchain local storage holding the url of the telemetry endpoint of the node
+pub const TELEMETRY_ENDPOINT_KEY: &[u8] = b"wivsupplychain::telemetry-endpoint";
+
+/// Time allowed to the telemetry endpoint to answer, in milliseconds
+const TELEMETRY_TIMEOUT: u64 = 2_000;
+
+/// Crypto of the custodian accounts signing the environment readings, their keys are kept
+/// in the keystore of the node
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+	use sp_runtime::{app_crypto::{app_crypto, sr25519}, MultiSignature, MultiSigner};
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signer of the environment readings in a runtime using `MultiSignature`
+	pub struct TelemetryAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TelemetryAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = Sr25519Signature;
+		type GenericPublic = Sr25519Public;
+	}
+}
+
+/// Metadata of an asset, decoded from its data
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct AssetMetadata {
+	/// producer of the asset
+	pub producer: Vec<u8>,
+	/// vintage year, none for non-vintage assets
+	pub vintage: Option<u16>,
+	/// format of the asset (e.g. 750ml, magnum, case of 12)
+	pub format: Vec<u8>,
+	/// production lot
+	pub lot: Vec<u8>,
+	/// storage location
+	pub location: Vec<u8>,
+	/// quantity of the asset, in the unit of its lot (e.g. centiliters, bottles), 1 by default
+	pub quantity: u64,
+}
+
+/// Json document carried by the asset data, fields are optional to report the missing ones
+#[derive(Deserialize)]
+#[serde(crate = "alt_serde")]
+struct AssetDocument {
+	producer: Option<String>,
+	vintage: Option<u16>,
+	format: Option<String>,
+	lot: Option<String>,
+	location: Option<String>,
+	quantity: Option<u64>,
+}
+
+/// Asset registered by a batch
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct AssetInput {
+	/// asset data (base64 encoding + json)
+	pub data: Vec<u8>,
+}
+
+/// Anchor of a document kept off chain
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct DocumentAnchor {
+	/// blake2-256 hash of the document
+	pub digest: H256,
+	/// location of the document
+	pub uri: Vec<u8>,
+}
+
+/// x25519 public key used to wrap the keys of confidential metadata
+pub type EncryptionKey = [u8; 32];
+
+/// Maximum length of a wrapped key
+pub const MAX_WRAPPED_KEY_LENGTH: usize = 128;
+
+/// Metadata of an asset only readable by the accounts it has been disclosed to
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct ConfidentialMetadata<AccountId, Balance> {
+	/// metadata encrypted with a symmetric key
+	pub payload: Vec<u8>,
+	/// symmetric key wrapped with the encryption key of each recipient
+	pub wrapped_keys: Vec<(AccountId, Vec<u8>)>,
+	/// part of the asset deposit reserved for the confidential metadata
+	pub deposit: Balance,
+}
+
+/// Lifecycle status of an asset
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum AssetStatus {
+	/// the asset has been registered and never handed over to a custodian
+	Registered,
+	/// a custody handover of the asset is pending
+	InTransit,
+	/// the asset has been handed over to a custodian
+	InStorage,
+	/// the asset is offered for sale or up for auction
+	Listed,
+	/// the asset has been consumed and removed
+	Consumed,
+	/// the asset has been destroyed and removed
+	Destroyed,
+	/// the custodian has reported the asset lost
+	Lost,
+}
+
+impl Default for AssetStatus {
+	fn default() -> Self {
+		AssetStatus::Registered
+	}
+}
+
+impl AssetStatus {
+	/// Transitions allowed by the lifecycle of the assets, a listed asset or an asset in transit
+	/// goes back to the status it had once its sale or handover is withdrawn
+	pub fn can_become(self, next: AssetStatus) -> bool {
+		use AssetStatus::*;
+		match (self, next) {
+			(Registered, InTransit) | (Registered, InStorage) | (Registered, Listed) => true,
+			(InStorage, InTransit) | (InStorage, InStorage) | (InStorage, Listed) => true,
+			// the receiver accepts the handover
+			(InTransit, InStorage) => true,
+			// the seller changes its price
+			(Listed, Listed) => true,
+			// a lost asset is only stored again by `report_found`
+			(Registered, Lost) | (InStorage, Lost) | (InTransit, Lost) => true,
+			(Registered, Consumed) | (InStorage, Consumed) => true,
+			(Registered, Destroyed) | (InStorage, Destroyed) | (Lost, Destroyed) => true,
+			_ => false,
+		}
+	}
+
+	/// Whether the asset is neither listed, in transit nor lost, it can then be packed, split or withdrawn
+	pub fn is_at_rest(self) -> bool {
+		matches!(self, AssetStatus::Registered | AssetStatus::InStorage)
+	}
+}
+
+/// Asset record stored on chain
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct AssetDetails<AccountId, Balance> {
+	/// current owner of the asset
+	pub owner: AccountId,
+	/// account holding the asset, e.g. the warehouse storing it
+	pub custodian: AccountId,
+	/// asset data (base64 encoding + json), empty when the document is kept off chain
+	pub data: Vec<u8>,
+	/// metadata decoded from the asset data
+	pub metadata: AssetMetadata,
+	/// anchor of the document when it is kept off chain
+	pub anchor: Option<DocumentAnchor>,
+	/// deposit reserved from the owner for the storage of the asset
+	pub deposit: Balance,
+	/// lifecycle status of the asset
+	pub status: AssetStatus,
+}
+
+pub type BalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+pub type AssetDetailsOf<T> = AssetDetails<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+pub type ConfidentialMetadataOf<T> = ConfidentialMetadata<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+/// Role of an account in the supply chain
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Role {
+	/// registers new assets
+	Producer,
+	/// takes custody of assets in a warehouse
+	Custodian,
+	/// attests the condition of assets
+	Inspector,
+	/// underwrites the assets
+	Insurer,
+}
+
+/// Reason recorded when an asset is removed from the chain
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum RemovalReason {
+	/// the asset has been consumed (e.g. a bottle has been opened)
+	Consumed,
+	/// the asset has been destroyed
+	Destroyed,
+	/// the asset has been withdrawn from the supply chain
+	Withdrawn,
+	/// the asset has been split into new assets
+	Split,
+	/// the asset has been merged with other assets into a new asset
+	Merged,
+}
+
+impl RemovalReason {
+	/// Final status of an asset removed for this reason, none when it lives on in other assets
+	/// or out of the supply chain
+	pub fn status(self) -> Option<AssetStatus> {
+		match self {
+			RemovalReason::Consumed => Some(AssetStatus::Consumed),
+			RemovalReason::Destroyed => Some(AssetStatus::Destroyed),
+			RemovalReason::Withdrawn | RemovalReason::Split | RemovalReason::Merged => None,
+		}
+	}
+}
+
+/// Reason an asset has been frozen by the force origin
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum FreezeReason {
+	/// the asset is involved in a suspected fraud
+	Fraud,
+	/// the asset is suspected to be counterfeit
+	Counterfeit,
+	/// the asset has been seized by a court or a customs authority
+	Seizure,
+	/// the asset belongs to a recalled lot, e.g. after a contamination
+	Recall,
+}
+
+/// Trace left on chain after the removal of an asset
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Tombstone<AccountId, BlockNumber> {
+	/// block in which the asset has been removed
+	pub block: BlockNumber,
+	/// account that removed the asset
+	pub removed_by: AccountId,
+	/// why the asset has been removed
+	pub reason: RemovalReason,
+}
+
+/// Action recorded in the provenance log of an asset
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProvenanceAction {
+	/// the asset has been registered
+	Created,
+	/// the asset has been transferred to the counterparty
+	Transferred,
+	/// the custody of the asset has been handed over to the counterparty
+	Stored,
+	/// the asset has been inspected
+	Inspected,
+	/// the asset has been removed
+	Removed,
+	/// the asset has been created from a part of the given asset
+	SplitFrom(AssetId),
+	/// the asset has been created by merging the given asset with others
+	MergedFrom(AssetId),
+	/// the asset has been stored outside its environment tolerance
+	EnvironmentBreach,
+	/// the asset has been fractionalised into the given number of shares
+	Fractionalised(u64),
+	/// every share of the asset has been redeemed by the actor
+	Redeemed,
+	/// the custodian has reported the asset lost
+	Lost,
+}
+
+/// Entry of the provenance log of an asset
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProvenanceEntry<AccountId, BlockNumber, Moment> {
+	/// what happened to the asset
+	pub action: ProvenanceAction,
+	/// account that performed the action
+	pub actor: AccountId,
+	/// other account involved in the action, if any
+	pub counterparty: Option<AccountId>,
+	/// block in which the action took place
+	pub block: BlockNumber,
+	/// timestamp of that block
+	pub timestamp: Moment,
+}
+
+/// Aspect of the condition of an asset covered by an attestation
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttestationKind {
+	/// level of the wine in the bottle
+	Ullage,
+	/// condition of the label
+	Label,
+	/// condition of the capsule
+	Capsule,
+	/// temperature at which the asset has been stored
+	StorageTemperature,
+}
+
+impl AttestationKind {
+	/// Every kind of attestation
+	pub const ALL: [AttestationKind; 4] = [
+		AttestationKind::Ullage,
+		AttestationKind::Label,
+		AttestationKind::Capsule,
+		AttestationKind::StorageTemperature,
+	];
+}
+
+/// Highest score of an attestation
+pub const MAX_SCORE: u8 = 100;
+
+/// Condition report signed by an inspector
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Attestation<AccountId, BlockNumber, Moment> {
+	/// aspect of the condition covered by the report
+	pub kind: AttestationKind,
+	/// inspector who signed the report
+	pub inspector: AccountId,
+	/// blake2-256 hash of the full report, kept off chain
+	pub data_hash: H256,
+	/// score given by the inspector, from 0 to `MAX_SCORE`
+	pub score: u8,
+	/// block in which the report has been signed
+	pub block: BlockNumber,
+	/// timestamp of that block
+	pub timestamp: Moment,
+}
+
+/// Environment of a storage location, aggregated over the samples of a polling interval
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct EnvironmentReading {
+	/// storage location, as in the metadata of the assets
+	pub location: Vec<u8>,
+	/// lowest temperature, in tenths of a degree Celsius
+	pub min_temperature: i16,
+	/// highest temperature, in tenths of a degree Celsius
+	pub max_temperature: i16,
+	/// lowest relative humidity, in tenths of a percent
+	pub min_humidity: u16,
+	/// highest relative humidity, in tenths of a percent
+	pub max_humidity: u16,
+	/// number of samples aggregated
+	pub samples: u32,
+}
+
+impl EnvironmentReading {
+	fn is_valid(&self) -> bool {
+		!self.location.is_empty() && self.samples > 0
+			&& self.min_temperature <= self.max_temperature && self.min_humidity <= self.max_humidity
+	}
+}
+
+/// Environment an asset must be stored in
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct EnvironmentTolerance {
+	/// lowest temperature, in tenths of a degree Celsius
+	pub min_temperature: i16,
+	/// highest temperature, in tenths of a degree Celsius
+	pub max_temperature: i16,
+	/// lowest relative humidity, in tenths of a percent
+	pub min_humidity: u16,
+	/// highest relative humidity, in tenths of a percent
+	pub max_humidity: u16,
+}
+
+impl EnvironmentTolerance {
+	fn is_valid(&self) -> bool {
+		self.min_temperature <= self.max_temperature && self.min_humidity <= self.max_humidity
+	}
+
+	/// Check that a reading stays within the tolerance
+	pub fn covers(&self, reading: &EnvironmentReading) -> bool {
+		reading.min_temperature >= self.min_temperature && reading.max_temperature <= self.max_temperature
+			&& reading.min_humidity >= self.min_humidity && reading.max_humidity <= self.max_humidity
+	}
+}
+
+/// Environment readings signed by a custodian, submitted by the offchain worker of its node
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TelemetryPayload<Public, BlockNumber> {
+	/// block at which the readings have been collected
+	pub block_number: BlockNumber,
+	/// aggregated readings, one per storage location
+	pub readings: Vec<EnvironmentReading>,
+	/// key of the custodian signing the readings
+	pub public: Public,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for TelemetryPayload<T::Public, T::BlockNumber> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+/// Sample returned by the telemetry endpoint, a json array of samples is expected, e.g.
+/// `[{"location":"London Bond","temperature":128,"humidity":705}]`
+#[derive(Deserialize)]
+#[serde(crate = "alt_serde")]
+struct SensorSample {
+	location: String,
+	/// temperature, in tenths of a degree Celsius
+	temperature: i16,
+	/// relative humidity, in tenths of a percent
+	humidity: u16,
+}
+
+/// Pending handover of the custody of an asset, waiting for the receiver to accept it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct TransferOffer<AccountId, BlockNumber> {
+	/// custodian handing over the asset
+	pub from: AccountId,
+	/// custodian receiving the asset
+	pub to: AccountId,
+	/// block at which the offer expires
+	pub expires: BlockNumber,
+	/// kind of attestation the asset must have recently received before the receiver accepts it
+	pub attestation: Option<AttestationKind>,
+}
+
+/// Containment tree of an asset, the assets packed in it and recursively
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AssetTree {
+	/// id of the asset
+	pub id: AssetId,
+	/// trees of the assets directly packed in it
+	pub children: Vec<AssetTree>,
+}
+
+/// Asset offered for sale at a fixed price
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Listing<AccountId, Balance> {
+	/// owner selling the asset
+	pub seller: AccountId,
+	/// price paid by the buyer, fees included
+	pub price: Balance,
+}
+
+/// Bidding mode of an auction
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuctionKind {
+	/// ascending auction, each bid must outbid the highest bid
+	English,
+	/// bids are committed as hashes until the bidding closes, then revealed
+	SealedBid,
+}
+
+/// Auction of an asset and of the assets packed in it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	/// owner selling the asset
+	pub seller: AccountId,
+	/// bidding mode
+	pub kind: AuctionKind,
+	/// lowest bid accepted
+	pub reserve_price: Balance,
+	/// first block at which bids are no longer accepted, the sealed bids are revealed from this block
+	pub bidding_end: BlockNumber,
+	/// block at which the auction closes
+	pub end: BlockNumber,
+	/// highest bid so far, reserved from the bidder
+	pub highest_bid: Option<(AccountId, Balance)>,
+	/// number of sealed bids committed
+	pub sealed_bids: u32,
+}
+
+/// Sealed bid committed in an auction
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct SealedBid<Balance> {
+	/// hash of the bidder, the amount and a salt, see `Module::bid_commitment`
+	pub commitment: H256,
+	/// amount reserved from the bidder until the auction closes, at least the amount of the bid
+	pub deposit: Balance,
+	/// amount of the bid, once revealed
+	pub revealed: Option<Balance>,
+}
+
+/// Unique identifier of an insurance policy
+pub type PolicyId = u64;
+
+/// Premiums the holder of a policy pays to keep its assets covered
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PremiumSchedule<Balance, BlockNumber> {
+	/// amount of each premium
+	pub amount: Balance,
+	/// number of blocks covered by each premium
+	pub period: BlockNumber,
+}
+
+/// Event that suspended the coverage of a policy
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum SuspensionReason {
+	/// a covered asset has been transferred to a new owner
+	Transferred,
+	/// a covered asset has been stored outside its environment tolerance
+	EnvironmentBreach,
+}
+
+/// Status of an insurance policy
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyStatus {
+	/// the assets are covered while the premiums are paid
+	Active,
+	/// the coverage is suspended until the insurer reinstates it
+	Suspended(SuspensionReason),
+	/// the policy has reached its expiry block
+	Expired,
+}
+
+/// Insurance policy issued by an insurer on assets
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Policy<AccountId, Balance, BlockNumber> {
+	/// account underwriting the assets, the coverage is reserved from it until the policy expires
+	pub insurer: AccountId,
+	/// owner of the assets, paying the premiums and filing the claims
+	pub holder: AccountId,
+	/// covered assets
+	pub assets: Vec<AssetId>,
+	/// total amount the insurer may pay out
+	pub coverage: Balance,
+	/// premiums due by the holder
+	pub premium: PremiumSchedule<Balance, BlockNumber>,
+	/// first block not covered by the premiums paid
+	pub paid_until: BlockNumber,
+	/// block at which the policy expires
+	pub expires: BlockNumber,
+	/// status of the policy
+	pub status: PolicyStatus,
+	/// amount already paid out
+	pub paid_out: Balance,
+}
+
+/// Claim filed by the holder of a policy, waiting for the insurer to settle it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Claim<AccountId, Balance, BlockNumber> {
+	/// holder who filed the claim, the payout goes to this account
+	pub claimant: AccountId,
+	/// amount claimed
+	pub amount: Balance,
+	/// blake2-256 hash of the evidence, kept off chain
+	pub evidence: H256,
+	/// block in which the claim has been filed
+	pub filed: BlockNumber,
+}
+
+pub type TransferOfferOf<T> =
+	TransferOffer<<T as frame_system::Trait>::AccountId, <T as frame_system::Trait>::BlockNumber>;
+
+pub type ProvenanceEntryOf<T> = ProvenanceEntry<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as pallet_timestamp::Trait>::Moment,
+>;
+
+pub type AttestationOf<T> = Attestation<
+	<T as frame_system::Trait>::AccountId,
+	<T as frame_system::Trait>::BlockNumber,
+	<T as pallet_timestamp::Trait>::Moment,
+>;
+
+pub type ListingOf<T> = Listing<<T as frame_system::Trait>::AccountId, BalanceOf<T>>;
+
+pub type AuctionOf<T> =
+	Auction<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+pub type SealedBidOf<T> = SealedBid<BalanceOf<T>>;
+
+pub type NegativeImbalanceOf<T> =
+	<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+
+pub type PremiumScheduleOf<T> = PremiumSchedule<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+pub type PolicyOf<T> =
+	Policy<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+pub type ClaimOf<T> =
+	Claim<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
 /// pallet parameters definition
-pub trait Trait: frame_system::Trait {	
+pub trait Trait: frame_system::Trait + pallet_timestamp::Trait + SendTransactionTypes<Call<Self>> + SigningTypes {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+	/// Keys of the custodians signing the environment readings
+	type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+	/// Number of blocks between two polls of the telemetry endpoint
+	type TelemetryInterval: Get<Self::BlockNumber>;
+	/// Maximum number of storage locations in a telemetry submission
+	type MaxReadings: Get<u32>;
+	/// Maximum number of assets with an environment tolerance at a storage location
+	type MaxMonitoredAssets: Get<u32>;
+	/// Priority of the telemetry submissions
+	type UnsignedPriority: Get<TransactionPriority>;
+	/// Minimum length of the asset data
+	type MinAssetLength: Get<u32>;
+	/// Maximum length of the asset data
+	type MaxAssetLength: Get<u32>;
+	/// Maximum length of the uri of an off chain document
+	type MaxUriLength: Get<u32>;
+	/// Maximum number of assets registered by a batch
+	type MaxBatchSize: Get<u32>;
+	/// Maximum number of accounts the confidential metadata of an asset is disclosed to
+	type MaxRecipients: Get<u32>;
+	/// Maximum number of levels of assets packed below a top-level asset
+	type MaxNestingDepth: Get<u32>;
+	/// Maximum number of assets directly packed in an asset
+	type MaxChildren: Get<u32>;
+	/// Origin allowed to grant and revoke roles
+	type AdminOrigin: EnsureOrigin<Self::Origin>;
+	/// Origin allowed to freeze, thaw and recall assets, e.g. a regulator
+	type ForceOrigin: EnsureOrigin<Self::Origin>;
+	/// Maximum number of assets frozen by a recall
+	type MaxRecall: Get<u32>;
+	/// Number of blocks after which a pending custody offer expires
+	type OfferExpiry: Get<Self::BlockNumber>;
+	/// Number of blocks during which an attestation is recent enough for a custody handover
+	type AttestationValidity: Get<Self::BlockNumber>;
+	/// Maximum number of policies covering an asset
+	type MaxPolicies: Get<u32>;
+	/// Maximum number of policies expiring in a block
+	type MaxPolicyExpiries: Get<u32>;
+	/// Part of the price of a sale paid to the platform
+	type MarketplaceFee: Get<Permill>;
+	/// Part of the price of a sale paid to the producer of the asset
+	type RoyaltyFee: Get<Permill>;
+	/// Account receiving the marketplace fees
+	type PlatformAccount: Get<Self::AccountId>;
+	/// Maximum number of blocks during which an auction takes bids
+	type MaxAuctionDuration: Get<Self::BlockNumber>;
+	/// Number of blocks during which the sealed bids are revealed once the bidding has closed
+	type RevealPeriod: Get<Self::BlockNumber>;
+	/// Maximum number of sealed bids in an auction
+	type MaxSealedBids: Get<u32>;
+	/// Maximum number of auctions ending in a block
+	type MaxAuctionsPerBlock: Get<u32>;
+	/// Currency in which the storage deposits are reserved
+	type Currency: ReservableCurrency<Self::AccountId>;
+	/// Deposit reserved for each stored asset
+	type AssetDepositBase: Get<BalanceOf<Self>>;
+	/// Additional deposit reserved for each byte of asset data
+	type AssetDepositPerByte: Get<BalanceOf<Self>>;
+	/// Weight information for the extrinsics of this pallet
+	type WeightInfo: WeightInfo;
 }
 
 // The runtime storage definition
 decl_storage! {
 	trait Store for Module<T: Trait> as WivSupplyChain {
-		Asset: map hasher(twox_64_concat) T::AccountId => Option<Vec<u8>>;
+		/// Assets by their unique id
+		Asset get(fn asset): map hasher(twox_64_concat) AssetId => Option<AssetDetailsOf<T>>;
+		/// Index of the assets owned by each account
+		AssetsByOwner: double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId => ();
+		/// Index of the assets in custody of each account
+		AssetsByCustodian: double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId => ();
+		/// Tombstones of the removed assets
+		Tombstones get(fn tombstone): map hasher(twox_64_concat) AssetId => Option<Tombstone<T::AccountId, T::BlockNumber>>;
+		/// Append-only provenance log of each asset, indexed by position
+		History: double_map hasher(twox_64_concat) AssetId, hasher(twox_64_concat) u32 => Option<ProvenanceEntryOf<T>>;
+		/// Number of entries in the provenance log of each asset
+		HistoryLength get(fn history_length): map hasher(twox_64_concat) AssetId => u32;
+		/// Id to be assigned to the next asset
+		NextAssetId get(fn next_asset_id): AssetId;
+		/// Status a listed asset or an asset in transit goes back to once its sale or handover is withdrawn
+		PriorStatus: map hasher(twox_64_concat) AssetId => Option<AssetStatus>;
+		/// Pending custody handovers
+		Offers get(fn offer): map hasher(twox_64_concat) AssetId => Option<TransferOfferOf<T>>;
+		/// Assets whose custody handover expires at a given block
+		OfferExpiries: map hasher(twox_64_concat) T::BlockNumber => Vec<AssetId>;
+		/// Condition reports of each asset, indexed by position in time order
+		Attestations: double_map hasher(twox_64_concat) AssetId, hasher(twox_64_concat) u32 => Option<AttestationOf<T>>;
+		/// Number of condition reports of each asset
+		AttestationCount get(fn attestation_count): map hasher(twox_64_concat) AssetId => u32;
+		/// Position of the latest condition report of each kind
+		LatestAttestations: double_map hasher(twox_64_concat) AssetId, hasher(twox_64_concat) AttestationKind => Option<u32>;
+		/// Environment each asset must be stored in
+		Tolerances get(fn tolerance): map hasher(twox_64_concat) AssetId => Option<EnvironmentTolerance>;
+		/// Assets with an environment tolerance, by storage location
+		MonitoredAssets: double_map hasher(blake2_128_concat) Vec<u8>, hasher(twox_64_concat) AssetId => ();
+		/// Number of assets with an environment tolerance at each storage location
+		MonitoredCount get(fn monitored_count): map hasher(blake2_128_concat) Vec<u8> => u32;
+		/// Latest environment reading of each storage location, with the block it has been reported in
+		LatestReadings get(fn latest_reading): map hasher(blake2_128_concat) Vec<u8> => Option<(T::BlockNumber, EnvironmentReading)>;
+		/// Block at which the latest readings of each custodian have been collected
+		LastTelemetry get(fn last_telemetry): map hasher(blake2_128_concat) T::AccountId => Option<T::BlockNumber>;
+		/// Encryption keys registered by the accounts
+		EncryptionKeys get(fn encryption_key): map hasher(blake2_128_concat) T::AccountId => Option<EncryptionKey>;
+		/// Confidential metadata of the assets
+		Confidential get(fn confidential): map hasher(twox_64_concat) AssetId => Option<ConfidentialMetadataOf<T>>;
+		/// Asset in which an asset is packed
+		Parent get(fn parent_of): map hasher(twox_64_concat) AssetId => Option<AssetId>;
+		/// Assets directly packed in an asset
+		Children get(fn children_of): map hasher(twox_64_concat) AssetId => Vec<AssetId>;
+		/// Assets from which an asset has been split or merged
+		Sources get(fn sources_of): map hasher(twox_64_concat) AssetId => Vec<AssetId>;
+		/// Account that registered each asset, receiving the royalties of its sales
+		Producers get(fn producer_of): map hasher(twox_64_concat) AssetId => Option<T::AccountId>;
+		/// Index of the assets not frozen of each producer named in the asset data
+		AssetsByProducer: double_map hasher(blake2_128_concat) Vec<u8>, hasher(twox_64_concat) AssetId => ();
+		/// Index of the assets not frozen of each lot of a producer
+		AssetsByLot: double_map hasher(blake2_128_concat) (Vec<u8>, Vec<u8>), hasher(twox_64_concat) AssetId => ();
+		/// Assets frozen by the force origin, with the reason of the freeze
+		Frozen get(fn frozen): map hasher(twox_64_concat) AssetId => Option<FreezeReason>;
+		/// Assets offered for sale
+		Listings get(fn listing): map hasher(twox_64_concat) AssetId => Option<ListingOf<T>>;
+		/// Running auctions
+		Auctions get(fn auction): map hasher(twox_64_concat) AssetId => Option<AuctionOf<T>>;
+		/// Auctions closing at a given block
+		AuctionEnds: map hasher(twox_64_concat) T::BlockNumber => Vec<AssetId>;
+		/// Sealed bids committed in each auction
+		SealedBids get(fn sealed_bid):
+			double_map hasher(twox_64_concat) AssetId, hasher(blake2_128_concat) T::AccountId => Option<SealedBidOf<T>>;
+		/// Number of shares each fractionalised asset is divided into
+		ShareSupply get(fn share_supply): map hasher(twox_64_concat) AssetId => Option<u64>;
+		/// Shares of the fractionalised assets held by each account
+		Shares get(fn shares): double_map hasher(twox_64_concat) AssetId, hasher(blake2_128_concat) T::AccountId => u64;
+		/// Insurance policies by their unique id
+		Policies get(fn policy): map hasher(twox_64_concat) PolicyId => Option<PolicyOf<T>>;
+		/// Id to be assigned to the next policy
+		NextPolicyId get(fn next_policy_id): PolicyId;
+		/// Policies covering each asset, until they expire
+		AssetPolicies get(fn policies_of): map hasher(twox_64_concat) AssetId => Vec<PolicyId>;
+		/// Policies expiring at a given block
+		PolicyExpiries: map hasher(twox_64_concat) T::BlockNumber => Vec<PolicyId>;
+		/// Pending claim of each policy
+		Claims get(fn claim): map hasher(twox_64_concat) PolicyId => Option<ClaimOf<T>>;
+		/// Roles granted to each account
+		Roles get(fn has_role): double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) Role => bool;
+	}
+	add_extra_genesis {
+		config(roles): Vec<(T::AccountId, Role)>;
+		build(|config: &GenesisConfig<T>| {
+			for (who, role) in config.roles.iter() {
+				<Roles<T>>::insert(who, role, true);
+			}
+		});
 	}
 }
 
 // Events generated from Wiv-Supplychain pallet
 decl_event!(
-	pub enum Event<T> where AccountId = <T as frame_system::Trait>::AccountId {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+		Balance = BalanceOf<T>,
+	{
 		/// New asset has been stored (base64 encoding + json)
-		/// [Asset, AccountId]
-		NewAssetStored(Vec<u8>, AccountId),
-		/// Asset has been transferred (base64 encoding + json)
-		/// [Asset, AccountId]
-		AssetTransferred(Vec<u8>, AccountId),
-		/// Asset has been removed (base64 encoding + json)
-		/// [Asset, AccountId]
-		AssetRemoved(Vec<u8>, AccountId),
+		/// [AssetId, AccountId]
+		NewAssetStored(AssetId, AccountId),
+		/// Batch of assets has been stored, with consecutive ids
+		/// [first AssetId, count, AccountId]
+		AssetsBatchStored(AssetId, u32, AccountId),
+		/// Asset has been transferred to a new owner
+		/// [AssetId, from, to]
+		AssetTransferred(AssetId, AccountId, AccountId),
+		/// Asset has been split into new assets, with consecutive ids
+		/// [source AssetId, first new AssetId, count, AccountId]
+		AssetSplit(AssetId, AssetId, u32, AccountId),
+		/// Assets have been merged into a new asset
+		/// [source AssetIds, new AssetId, AccountId]
+		AssetsMerged(Vec<AssetId>, AssetId, AccountId),
+		/// Asset has been removed
+		/// [AssetId, AccountId, RemovalReason]
+		AssetRemoved(AssetId, AccountId, RemovalReason),
+		/// Lifecycle status of an asset has changed
+		/// [AssetId, AssetStatus]
+		StatusChanged(AssetId, AssetStatus),
+		/// Custody of an asset has been handed over to a new custodian
+		/// [AssetId, from, to]
+		CustodyTransferred(AssetId, AccountId, AccountId),
+		/// Custody of an asset has been offered to a receiver
+		/// [AssetId, from, to, expires]
+		TransferOffered(AssetId, AccountId, AccountId, BlockNumber),
+		/// Receiver has accepted the custody of an asset
+		/// [AssetId, from, to]
+		TransferAccepted(AssetId, AccountId, AccountId),
+		/// Receiver has rejected the custody of an asset
+		/// [AssetId, from, to]
+		TransferRejected(AssetId, AccountId, AccountId),
+		/// Custody offer has been cancelled by the custodian
+		/// [AssetId, from, to]
+		OfferCancelled(AssetId, AccountId, AccountId),
+		/// Custody offer has expired
+		/// [AssetId, from, to]
+		OfferExpired(AssetId, AccountId, AccountId),
+		/// Inspector has attested the condition of an asset
+		/// [AssetId, AttestationKind, score, inspector]
+		AssetAttested(AssetId, AttestationKind, u8, AccountId),
+		/// Environment an asset must be stored in has been set, none when it is no longer monitored
+		/// [AssetId, EnvironmentTolerance]
+		ToleranceSet(AssetId, Option<EnvironmentTolerance>),
+		/// Custodian has reported the environment of its storage locations
+		/// [custodian, number of locations]
+		EnvironmentReported(AccountId, u32),
+		/// Asset has been stored outside its environment tolerance
+		/// [AssetId, EnvironmentReading]
+		EnvironmentBreach(AssetId, EnvironmentReading),
+		/// Owner has offered an asset for sale
+		/// [AssetId, seller, price]
+		AssetListed(AssetId, AccountId, Balance),
+		/// Owner has withdrawn an asset from sale
+		/// [AssetId, seller]
+		AssetDelisted(AssetId, AccountId),
+		/// Asset has been sold, the fees have been paid out of the price
+		/// [AssetId, seller, buyer, price]
+		AssetSold(AssetId, AccountId, AccountId, Balance),
+		/// Owner has put an asset up for auction
+		/// [AssetId, seller, AuctionKind, closing block]
+		AuctionStarted(AssetId, AccountId, AuctionKind, BlockNumber),
+		/// Bid has been placed in an English auction, it is the highest bid
+		/// [AssetId, bidder, amount]
+		BidPlaced(AssetId, AccountId, Balance),
+		/// Sealed bid has been committed
+		/// [AssetId, bidder]
+		BidCommitted(AssetId, AccountId),
+		/// Sealed bid has been revealed
+		/// [AssetId, bidder, amount]
+		BidRevealed(AssetId, AccountId, Balance),
+		/// Seller has cancelled an auction without bids
+		/// [AssetId, seller]
+		AuctionCancelled(AssetId, AccountId),
+		/// Auction has closed, the asset has been sold to the winner if any
+		/// [AssetId, winner]
+		AuctionClosed(AssetId, Option<AccountId>),
+		/// Owner has fractionalised an asset, it holds every share
+		/// [AssetId, owner, shares]
+		AssetFractionalised(AssetId, AccountId, u64),
+		/// Shares of a fractionalised asset have been transferred
+		/// [AssetId, from, to, shares]
+		SharesTransferred(AssetId, AccountId, AccountId, u64),
+		/// Holder of every share has redeemed a fractionalised asset, it is its sole owner
+		/// [AssetId, AccountId]
+		AssetRedeemed(AssetId, AccountId),
+		/// Insurer has issued a policy on assets
+		/// [PolicyId, insurer, holder]
+		PolicyIssued(PolicyId, AccountId, AccountId),
+		/// Holder has paid a premium, the assets are covered until the given block
+		/// [PolicyId, amount, paid until]
+		PremiumPaid(PolicyId, Balance, BlockNumber),
+		/// Coverage of a policy has been suspended
+		/// [PolicyId, SuspensionReason]
+		PolicySuspended(PolicyId, SuspensionReason),
+		/// Insurer has reinstated the coverage of a policy for the current owner of its assets
+		/// [PolicyId, holder]
+		PolicyReinstated(PolicyId, AccountId),
+		/// Policy has expired, the remaining coverage is released
+		/// [PolicyId]
+		PolicyExpired(PolicyId),
+		/// Holder has filed a claim
+		/// [PolicyId, claimant, amount]
+		ClaimFiled(PolicyId, AccountId, Balance),
+		/// Insurer has settled a claim, a zero payout rejects it
+		/// [PolicyId, claimant, payout]
+		ClaimSettled(PolicyId, AccountId, Balance),
+		/// Assets have been packed in a parent asset
+		/// [parent, children, AccountId]
+		AssetsPacked(AssetId, Vec<AssetId>, AccountId),
+		/// Assets packed in a parent asset have been unpacked
+		/// [parent, children, AccountId]
+		AssetsUnpacked(AssetId, Vec<AssetId>, AccountId),
+		/// Account has registered its encryption key
+		/// [AccountId]
+		EncryptionKeyRegistered(AccountId),
+		/// Confidential metadata of an asset has been replaced
+		/// [AssetId, number of recipients]
+		ConfidentialMetadataSet(AssetId, u32),
+		/// Confidential metadata of an asset has been disclosed to an account
+		/// [AssetId, AccountId]
+		AccessGranted(AssetId, AccountId),
+		/// Account can no longer decrypt the confidential metadata of an asset
+		/// [AssetId, AccountId]
+		AccessRevoked(AssetId, AccountId),
+		/// Role has been granted to an account
+		/// [AccountId, Role]
+		RoleGranted(AccountId, Role),
+		/// Role has been revoked from an account
+		/// [AccountId, Role]
+		RoleRevoked(AccountId, Role),
+		/// Asset has been frozen, its owner and custodian can't move it until it is thawed
+		/// [AssetId, FreezeReason]
+		AssetFrozen(AssetId, FreezeReason),
+		/// Frozen asset has been thawed
+		/// [AssetId]
+		AssetThawed(AssetId),
+		/// Assets of a producer, or of one of its lots, have been recalled
+		/// [producer, lot, count]
+		AssetsRecalled(Vec<u8>, Option<Vec<u8>>, u32),
 	}
 );
 
 // Errors inform users that something went wrong.
 decl_error! {
 	pub enum Error for Module<T: Trait> {
-		NoneValue,
+		/// asset id is unknown
+		UnknownAsset,
+		/// the caller is not the owner of the asset
+		NotOwner,
+		/// the caller is not the custodian of the asset
+		NotCustodian,
+		/// asset has been removed
+		AssetRemoved,
+		/// the storage deposit can't be reserved from the caller
+		InsufficientDeposit,
+		/// the caller doesn't have the role required by the call
+		MissingRole,
+		/// the account already has this role
+		RoleAlreadyGranted,
+		/// the account doesn't have this role
+		RoleNotGranted,
+		/// a custody handover of the asset is already pending
+		PendingOffer,
+		/// no custody handover of the asset is pending
+		NoOffer,
+		/// the caller is not the receiver of the pending custody handover
+		NotReceiver,
+		/// no more asset ids are available
 		StorageOverflow,
 		/// asset data is too short
 		TooShort,
 		/// asset data is too long
 		TooLong,
+		/// asset data is not base64 encoded
+		InvalidBase64,
+		/// asset data is not a json document
+		InvalidJson,
+		/// a mandatory field is missing in the asset data
+		MissingField,
+		/// the batch doesn't contain any asset
+		EmptyBatch,
+		/// the batch contains too many assets
+		BatchTooLarge,
+		/// the asset is packed in another asset, it moves with it
+		AssetPacked,
+		/// other assets are still packed in the asset
+		NotEmpty,
+		/// the asset can't contain that many assets
+		TooManyChildren,
+		/// packing would nest the assets too deeply
+		TooDeep,
+		/// an asset can't be packed in itself or in an asset it contains
+		CyclicPacking,
+		/// the same asset is listed twice
+		DuplicateAsset,
+		/// the quantities of the assets don't add up
+		QuantityMismatch,
+		/// the quantity of an asset can't be zero
+		InvalidQuantity,
+		/// splits and merges can't be recorded as removals
+		InvalidReason,
+		/// the account hasn't registered an encryption key
+		NoEncryptionKey,
+		/// the asset has no confidential metadata
+		NoConfidentialMetadata,
+		/// the confidential metadata can't be disclosed to that many accounts
+		TooManyRecipients,
+		/// the same account is listed twice
+		DuplicateRecipient,
+		/// the wrapped key is empty or too long
+		InvalidWrappedKey,
+		/// the confidential metadata hasn't been disclosed to the account
+		NoAccess,
+		/// no asset to pack has been given
+		NothingToPack,
+		/// no asset is packed in the asset
+		NothingToUnpack,
+		/// the score of an attestation is above `MAX_SCORE`
+		InvalidScore,
+		/// the asset hasn't recently received the attestation required by the custody offer
+		MissingAttestation,
+		/// the lower bound of the tolerance is above its upper bound
+		InvalidTolerance,
+		/// the storage location of the asset is unknown
+		UnknownLocation,
+		/// the asset has no environment tolerance
+		NotMonitored,
+		/// too many assets are monitored at the storage location
+		TooManyMonitoredAssets,
+		/// the submission contains too many readings
+		TooManyReadings,
+		/// a reading has no location or no sample, or its bounds are inverted
+		InvalidReading,
+		/// readings collected at a later block have already been reported
+		StaleTelemetry,
+		/// the readings are collected at a future block
+		FutureTelemetry,
+		/// policy id is unknown
+		UnknownPolicy,
+		/// the caller is not the insurer of the policy
+		NotInsurer,
+		/// the caller is not the holder of the policy
+		NotHolder,
+		/// the amount can't be zero
+		InvalidAmount,
+		/// a premium must cover at least one block
+		InvalidPremium,
+		/// the expiry block of a policy must be in the future
+		InvalidExpiry,
+		/// the coverage can't be reserved from the insurer
+		InsufficientCollateral,
+		/// the asset is covered by too many policies
+		TooManyPolicies,
+		/// too many policies expire in that block
+		TooManyExpiries,
+		/// the policy has expired
+		PolicyExpired,
+		/// the coverage of the policy is suspended
+		CoverageSuspended,
+		/// the coverage of the policy is not suspended
+		NotSuspended,
+		/// the premiums paid don't cover the current block
+		PremiumDue,
+		/// the claim exceeds the remaining coverage
+		CoverageExceeded,
+		/// a claim on the policy is already pending
+		PendingClaim,
+		/// no claim on the policy is pending
+		NoClaim,
+		/// the payout exceeds the amount claimed
+		PayoutTooHigh,
+		/// the covered assets don't have a single owner
+		MultipleOwners,
+		/// the premiums paid already cover the policy until it expires
+		PremiumsPaid,
+		/// the asset is not offered for sale
+		NotListed,
+		/// the seller can't buy its own asset
+		OwnListing,
+		/// the buyer can't pay the price
+		InsufficientFunds,
+		/// the asset is up for auction
+		InAuction,
+		/// the asset is offered for sale at a fixed price
+		ForSale,
+		/// no auction of the asset is running
+		NoAuction,
+		/// an auction must take bids during one to `MaxAuctionDuration` blocks
+		InvalidDuration,
+		/// the auction doesn't take this kind of bid
+		WrongAuctionKind,
+		/// the auction no longer takes bids
+		BiddingClosed,
+		/// the sealed bids of the auction can't be revealed at this block
+		NotRevealing,
+		/// the bid is below the reserve price or doesn't outbid the highest bid
+		BidTooLow,
+		/// the bidder has already committed a sealed bid
+		AlreadyCommitted,
+		/// the auction has too many sealed bids
+		TooManyBids,
+		/// too many auctions end in that block
+		TooManyAuctions,
+		/// the bidder hasn't committed a sealed bid
+		NoBid,
+		/// the sealed bid has already been revealed
+		AlreadyRevealed,
+		/// the amount and salt don't match the commitment, or the amount exceeds the deposit
+		InvalidReveal,
+		/// the auction has received bids, it can't be cancelled
+		HasBids,
+		/// the asset is fractionalised, its shares are transferred instead
+		Fractionalised,
+		/// the asset is not fractionalised
+		NotFractionalised,
+		/// an asset is fractionalised into at least two shares
+		InvalidShares,
+		/// the account doesn't hold enough shares
+		InsufficientShares,
+		/// the caller doesn't hold every share of the asset
+		MissingShares,
+		/// the lifecycle of the asset doesn't allow this call in its current status
+		InvalidStateTransition,
+		/// the asset has not been reported lost
+		NotLost,
+		/// the asset, or an asset packed in it, is frozen
+		Frozen,
+		/// the asset is not frozen
+		NotFrozen,
+		/// no asset left to recall
+		NothingToRecall,
 	}
 }
 
@@ -53,52 +1132,1841 @@ decl_module! {
 	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
 		type Error = Error<T>;
 		fn deposit_event() = default;
-		/// New asset storage
-		#[weight = 500_000]
+		/// Minimum length of the asset data
+		const MinAssetLength: u32 = T::MinAssetLength::get();
+		/// Maximum length of the asset data
+		const MaxAssetLength: u32 = T::MaxAssetLength::get();
+		/// Deposit reserved for each stored asset
+		const AssetDepositBase: BalanceOf<T> = T::AssetDepositBase::get();
+		/// Additional deposit reserved for each byte of asset data
+		const AssetDepositPerByte: BalanceOf<T> = T::AssetDepositPerByte::get();
+		/// Maximum length of the uri of an off chain document
+		const MaxUriLength: u32 = T::MaxUriLength::get();
+		/// Maximum number of assets registered by a batch
+		const MaxBatchSize: u32 = T::MaxBatchSize::get();
+		/// Maximum number of accounts the confidential metadata of an asset is disclosed to
+		const MaxRecipients: u32 = T::MaxRecipients::get();
+		/// Maximum number of levels of assets packed below a top-level asset
+		const MaxNestingDepth: u32 = T::MaxNestingDepth::get();
+		/// Maximum number of assets directly packed in an asset
+		const MaxChildren: u32 = T::MaxChildren::get();
+		/// Number of blocks after which a pending custody offer expires
+		const OfferExpiry: T::BlockNumber = T::OfferExpiry::get();
+		/// Number of blocks during which an attestation is recent enough for a custody handover
+		const AttestationValidity: T::BlockNumber = T::AttestationValidity::get();
+		/// Number of blocks between two polls of the telemetry endpoint
+		const TelemetryInterval: T::BlockNumber = T::TelemetryInterval::get();
+		/// Maximum number of storage locations in a telemetry submission
+		const MaxReadings: u32 = T::MaxReadings::get();
+		/// Maximum number of assets with an environment tolerance at a storage location
+		const MaxMonitoredAssets: u32 = T::MaxMonitoredAssets::get();
+		/// Maximum number of policies covering an asset
+		const MaxPolicies: u32 = T::MaxPolicies::get();
+		/// Maximum number of policies expiring in a block
+		const MaxPolicyExpiries: u32 = T::MaxPolicyExpiries::get();
+		/// Part of the price of a sale paid to the platform
+		const MarketplaceFee: Permill = T::MarketplaceFee::get();
+		/// Part of the price of a sale paid to the producer of the asset
+		const RoyaltyFee: Permill = T::RoyaltyFee::get();
+		/// Maximum number of blocks during which an auction takes bids
+		const MaxAuctionDuration: T::BlockNumber = T::MaxAuctionDuration::get();
+		/// Number of blocks during which the sealed bids are revealed once the bidding has closed
+		const RevealPeriod: T::BlockNumber = T::RevealPeriod::get();
+		/// Maximum number of sealed bids in an auction
+		const MaxSealedBids: u32 = T::MaxSealedBids::get();
+		/// Maximum number of auctions ending in a block
+		const MaxAuctionsPerBlock: u32 = T::MaxAuctionsPerBlock::get();
+
+		/// Expire the custody offers and the insurance policies reaching their end,
+		/// the weight of the auctions closed in `on_finalize` is accounted for here
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let expiring = <OfferExpiries<T>>::take(now);
+			for asset_id in expiring.iter() {
+				match Self::offer(asset_id) {
+					// offers accepted, rejected or cancelled in the meantime are gone
+					Some(offer) if offer.expires == now => {
+						<Offers<T>>::remove(asset_id);
+						Self::restore_status_of(*asset_id);
+						Self::deposit_event(RawEvent::OfferExpired(*asset_id, offer.from, offer.to));
+					}
+					_ => {}
+				}
+			}
+			let policies = <PolicyExpiries<T>>::take(now);
+			for &policy_id in policies.iter() {
+				Self::expire_policy(policy_id);
+			}
+			let closing = <AuctionEnds<T>>::decode_len(now).unwrap_or(0) as u32;
+			T::WeightInfo::on_initialize(expiring.len() as u32, policies.len() as u32)
+				.saturating_add(T::WeightInfo::on_finalize(closing))
+		}
+
+		/// Close the auctions reaching their end
+		fn on_finalize(now: T::BlockNumber) {
+			for asset_id in <AuctionEnds<T>>::take(now) {
+				Self::close_auction(asset_id, now);
+			}
+		}
+
+		/// Poll the telemetry endpoint of the node and submit the readings of its storage locations
+		fn offchain_worker(block_number: T::BlockNumber) {
+			if let Err(error) = Self::report_environment(block_number) {
+				debug::warn!("wivsupplychain: environment not reported: {}", error);
+			}
+		}
+
+		/// New asset storage, the id assigned to the asset is emitted in `NewAssetStored`
+		#[weight = T::WeightInfo::new_asset(asset.len() as u32)]
 		pub fn new_asset(origin, asset: Vec<u8>) -> dispatch::DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			let sender = ensure_signed(origin)?;
-			ensure!(asset.len() >= 16, Error::<T>::TooShort); //check minimum length
-			ensure!(asset.len() <= 8192, Error::<T>::TooLong);  // check maximum length
+			Self::ensure_role(&sender, Role::Producer)?;
+			let metadata = Self::validate_asset(&asset)?;
+			// Find a new asset id
+			let asset_ids = Self::free_asset_ids(1)?;
+			let asset_id = asset_ids.start;
+			// Reserve the storage deposit
+			let deposit = Self::deposit_for(asset.len(), &metadata);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
 			// Update storage.
-			let assetstorage=asset.clone();
-			<Asset<T>>::insert(&sender, assetstorage);
+			Self::insert_asset(asset_id, &sender, asset, metadata, None, deposit, Some(&sender));
+			NextAssetId::put(asset_ids.end);
 			// Emit an event
-			Self::deposit_event(RawEvent::NewAssetStored(asset, sender));
+			Self::deposit_event(RawEvent::NewAssetStored(asset_id, sender));
 			// Return a successful DispatchResult
 			Ok(())
 		}
-		/// Transfer of an asset
-		#[weight = 500_000]
-		pub fn transfer_asset(origin, asset: Vec<u8>) -> dispatch::DispatchResult {
+		/// New asset storage, only the hash of its document is kept on chain
+		#[weight = T::WeightInfo::new_anchored_asset(uri.len() as u32)]
+		pub fn new_anchored_asset(origin, digest: H256, uri: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_role(&sender, Role::Producer)?;
+			ensure!(!uri.is_empty(), Error::<T>::TooShort);
+			ensure!(uri.len() <= T::MaxUriLength::get() as usize, Error::<T>::TooLong);
+			let asset_ids = Self::free_asset_ids(1)?;
+			let asset_id = asset_ids.start;
+			let metadata = AssetMetadata { quantity: 1, ..Default::default() };
+			// The digest is stored along with the uri
+			let deposit = Self::deposit_for(H256::len_bytes().saturating_add(uri.len()), &metadata);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			let anchor = DocumentAnchor { digest, uri };
+			Self::insert_asset(asset_id, &sender, Vec::new(), metadata, Some(anchor), deposit, Some(&sender));
+			NextAssetId::put(asset_ids.end);
+			Self::deposit_event(RawEvent::NewAssetStored(asset_id, sender));
+			Ok(())
+		}
+		/// New assets storage, the whole batch is stored or none of its assets
+		#[weight = T::WeightInfo::new_assets_batch(assets.len() as u32)]
+		pub fn new_assets_batch(origin, assets: Vec<AssetInput>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_role(&sender, Role::Producer)?;
+			ensure!(!assets.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(assets.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+			// Validate every asset before touching storage
+			let mut deposit = BalanceOf::<T>::zero();
+			let mut validated = Vec::with_capacity(assets.len());
+			for asset in assets {
+				let metadata = Self::validate_asset(&asset.data)?;
+				let asset_deposit = Self::deposit_for(asset.data.len(), &metadata);
+				deposit = deposit.saturating_add(asset_deposit);
+				validated.push((asset.data, metadata, asset_deposit));
+			}
+			let count = validated.len() as u32;
+			let asset_ids = Self::free_asset_ids(count)?;
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			// Update storage.
+			for (asset_id, (data, metadata, asset_deposit)) in asset_ids.clone().zip(validated) {
+				Self::insert_asset(asset_id, &sender, data, metadata, None, asset_deposit, Some(&sender));
+				Self::deposit_event(RawEvent::NewAssetStored(asset_id, sender.clone()));
+			}
+			NextAssetId::put(asset_ids.end);
+			Self::deposit_event(RawEvent::AssetsBatchStored(asset_ids.start, count, sender));
+			Ok(())
+		}
+		/// Transfer of an asset and of the assets packed in it to a new owner,
+		/// the custody of the assets is unchanged
+		#[weight = T::WeightInfo::transfer_asset(<Module<T>>::max_descendants(), T::MaxAssetLength::get())]
+		pub fn transfer_asset(origin, asset_id: AssetId, to: T::AccountId) -> dispatch::DispatchResultWithPostInfo {
 			// Check that the extrinsic was signed and get the signer.
 			let sender = ensure_signed(origin)?;
-			ensure!(asset.len() >= 16, Error::<T>::TooShort); //check minimum length
-			ensure!(asset.len() <= 8192, Error::<T>::TooLong);  // check maximum length
+			// Check the property of the asset
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			ensure!(!<Auctions<T>>::contains_key(asset_id), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(asset_id), Error::<T>::Fractionalised);
+			// A lost asset changes hands once it is found
+			ensure!(details.status != AssetStatus::Lost, Error::<T>::InvalidStateTransition);
+			let length = details.data.len() as u32;
+			let assets = Self::tree_details(asset_id)?;
+			Self::reserve_deposits(&assets, &to)?;
 			// Update storage.
-			let assettransfer=asset.clone();
-			//todo: add control on previous asset/property
-			<Asset<T>>::insert(&sender, assettransfer);
+			let moved = assets.len() as u32 - 1;
+			for (id, details) in assets {
+				Self::do_transfer(id, details, &to);
+			}
 			// Emit an event
-			Self::deposit_event(RawEvent::AssetTransferred(asset, sender));
-			// Return a successful DispatchResult
+			Self::deposit_event(RawEvent::AssetTransferred(asset_id, sender, to));
+			// Return the weight of the assets actually moved
+			Ok(Some(T::WeightInfo::transfer_asset(moved, length)).into())
+		}
+		/// Offer an asset and the assets packed in it for sale, or change the price of its offer
+		#[weight = T::WeightInfo::list_for_sale()]
+		pub fn list_for_sale(origin, asset_id: AssetId, price: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			ensure!(!<Auctions<T>>::contains_key(asset_id), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(asset_id), Error::<T>::Fractionalised);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(!price.is_zero(), Error::<T>::InvalidAmount);
+			Self::ensure_transition(&details, AssetStatus::Listed)?;
+			Self::set_status(asset_id, &mut details, AssetStatus::Listed);
+			<Asset<T>>::insert(asset_id, details);
+			<Listings<T>>::insert(asset_id, Listing { seller: sender.clone(), price });
+			Self::deposit_event(RawEvent::AssetListed(asset_id, sender, price));
+			Ok(())
+		}
+		/// Withdraw an asset from sale
+		#[weight = T::WeightInfo::delist()]
+		pub fn delist(origin, asset_id: AssetId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let listing = Self::listing(asset_id).ok_or(Error::<T>::NotListed)?;
+			ensure!(listing.seller == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			<Listings<T>>::remove(asset_id);
+			Self::restore_status_of(asset_id);
+			Self::deposit_event(RawEvent::AssetDelisted(asset_id, sender));
 			Ok(())
 		}
-		/// Transfer of an asset
-		#[weight = 500_000]
-		pub fn remove_asset(origin, asset: Vec<u8>) -> dispatch::DispatchResult {
+		/// Buy an asset offered for sale, the price is paid and the ownership of the asset
+		/// and of the assets packed in it is transferred at once
+		#[weight = T::WeightInfo::buy(<Module<T>>::max_descendants())]
+		#[transactional]
+		pub fn buy(origin, asset_id: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let listing = Self::listing(asset_id).ok_or(Error::<T>::NotListed)?;
+			ensure!(listing.seller != sender, Error::<T>::OwnListing);
+			Self::live_asset(asset_id)?;
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			let assets = Self::tree_details(asset_id)?;
+			Self::reserve_deposits(&assets, &sender)?;
+			let payment = T::Currency::withdraw(
+				&sender,
+				listing.price,
+				WithdrawReason::Transfer.into(),
+				ExistenceRequirement::KeepAlive,
+			).map_err(|_| Error::<T>::InsufficientFunds)?;
+			Self::pay_sale(asset_id, &listing.seller, payment, listing.price);
+			// Update storage.
+			let moved = assets.len() as u32 - 1;
+			for (id, details) in assets {
+				Self::do_transfer(id, details, &sender);
+			}
+			Self::deposit_event(RawEvent::AssetSold(asset_id, listing.seller, sender, listing.price));
+			Ok(Some(T::WeightInfo::buy(moved)).into())
+		}
+		/// Put an asset and the assets packed in it up for auction, bids are taken during `duration` blocks
+		/// and sealed bids are then revealed during `RevealPeriod` blocks
+		#[weight = T::WeightInfo::start_auction()]
+		pub fn start_auction(
+			origin,
+			asset_id: AssetId,
+			kind: AuctionKind,
+			reserve_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			ensure!(!<Listings<T>>::contains_key(asset_id), Error::<T>::ForSale);
+			ensure!(!<Auctions<T>>::contains_key(asset_id), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(asset_id), Error::<T>::Fractionalised);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(
+				!duration.is_zero() && duration <= T::MaxAuctionDuration::get(),
+				Error::<T>::InvalidDuration
+			);
+			Self::ensure_transition(&details, AssetStatus::Listed)?;
+			let bidding_end = <frame_system::Module<T>>::block_number().saturating_add(duration);
+			let end = match kind {
+				AuctionKind::English => bidding_end,
+				AuctionKind::SealedBid => bidding_end.saturating_add(T::RevealPeriod::get()),
+			};
+			ensure!(
+				<AuctionEnds<T>>::decode_len(end).unwrap_or(0) < T::MaxAuctionsPerBlock::get() as usize,
+				Error::<T>::TooManyAuctions
+			);
+			// Update storage.
+			Self::set_status(asset_id, &mut details, AssetStatus::Listed);
+			<Asset<T>>::insert(asset_id, details);
+			<Auctions<T>>::insert(asset_id, Auction {
+				seller: sender.clone(),
+				kind,
+				reserve_price,
+				bidding_end,
+				end,
+				highest_bid: None,
+				sealed_bids: 0,
+			});
+			<AuctionEnds<T>>::append(end, asset_id);
+			Self::deposit_event(RawEvent::AuctionStarted(asset_id, sender, kind, end));
+			Ok(())
+		}
+		/// Bid in an English auction, the amount is reserved until the bid is outbid
+		#[weight = T::WeightInfo::bid()]
+		pub fn bid(origin, asset_id: AssetId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auction(asset_id).ok_or(Error::<T>::NoAuction)?;
+			ensure!(auction.kind == AuctionKind::English, Error::<T>::WrongAuctionKind);
+			ensure!(<frame_system::Module<T>>::block_number() < auction.bidding_end, Error::<T>::BiddingClosed);
+			ensure!(auction.seller != sender, Error::<T>::OwnListing);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(!amount.is_zero() && amount >= auction.reserve_price, Error::<T>::BidTooLow);
+			// The highest bidder raising its bid only reserves the difference
+			let reserved = match &auction.highest_bid {
+				Some((bidder, highest)) => {
+					ensure!(amount > *highest, Error::<T>::BidTooLow);
+					if *bidder == sender { *highest } else { Zero::zero() }
+				}
+				None => Zero::zero(),
+			};
+			T::Currency::reserve(&sender, amount - reserved).map_err(|_| Error::<T>::InsufficientFunds)?;
+			// Update storage.
+			if let Some((outbid, highest)) = auction.highest_bid.replace((sender.clone(), amount)) {
+				if outbid != sender {
+					T::Currency::unreserve(&outbid, highest);
+				}
+			}
+			<Auctions<T>>::insert(asset_id, auction);
+			Self::deposit_event(RawEvent::BidPlaced(asset_id, sender, amount));
+			Ok(())
+		}
+		/// Commit a sealed bid, `commitment` is the `bid_commitment` of the bid and `deposit`,
+		/// hiding the amount of the bid, is reserved until the auction closes
+		#[weight = T::WeightInfo::commit_bid()]
+		pub fn commit_bid(
+			origin,
+			asset_id: AssetId,
+			commitment: H256,
+			deposit: BalanceOf<T>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auction(asset_id).ok_or(Error::<T>::NoAuction)?;
+			ensure!(auction.kind == AuctionKind::SealedBid, Error::<T>::WrongAuctionKind);
+			ensure!(<frame_system::Module<T>>::block_number() < auction.bidding_end, Error::<T>::BiddingClosed);
+			ensure!(auction.seller != sender, Error::<T>::OwnListing);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(!<SealedBids<T>>::contains_key(asset_id, &sender), Error::<T>::AlreadyCommitted);
+			ensure!(auction.sealed_bids < T::MaxSealedBids::get(), Error::<T>::TooManyBids);
+			ensure!(!deposit.is_zero(), Error::<T>::InvalidAmount);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientFunds)?;
+			// Update storage.
+			auction.sealed_bids = auction.sealed_bids.saturating_add(1);
+			<Auctions<T>>::insert(asset_id, auction);
+			<SealedBids<T>>::insert(asset_id, &sender, SealedBid { commitment, deposit, revealed: None });
+			Self::deposit_event(RawEvent::BidCommitted(asset_id, sender));
+			Ok(())
+		}
+		/// Reveal a sealed bid once the bidding has closed, the highest bid revealed wins the auction
+		#[weight = T::WeightInfo::reveal_bid()]
+		pub fn reveal_bid(origin, asset_id: AssetId, amount: BalanceOf<T>, salt: H256) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut auction = Self::auction(asset_id).ok_or(Error::<T>::NoAuction)?;
+			ensure!(auction.kind == AuctionKind::SealedBid, Error::<T>::WrongAuctionKind);
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(auction.bidding_end <= now && now < auction.end, Error::<T>::NotRevealing);
+			let mut bid = Self::sealed_bid(asset_id, &sender).ok_or(Error::<T>::NoBid)?;
+			ensure!(bid.revealed.is_none(), Error::<T>::AlreadyRevealed);
+			ensure!(
+				bid.commitment == Self::bid_commitment(&sender, amount, &salt) && amount <= bid.deposit,
+				Error::<T>::InvalidReveal
+			);
+			// Bids below the reserve price are revealed but can't win, ties go to the first bid revealed
+			let highest = !amount.is_zero()
+				&& amount >= auction.reserve_price
+				&& auction.highest_bid.as_ref().map_or(true, |(_, highest)| amount > *highest);
+			// Update storage.
+			bid.revealed = Some(amount);
+			<SealedBids<T>>::insert(asset_id, &sender, bid);
+			if highest {
+				auction.highest_bid = Some((sender.clone(), amount));
+				<Auctions<T>>::insert(asset_id, auction);
+			}
+			Self::deposit_event(RawEvent::BidRevealed(asset_id, sender, amount));
+			Ok(())
+		}
+		/// Cancel an auction that hasn't received any bid
+		#[weight = T::WeightInfo::cancel_auction()]
+		pub fn cancel_auction(origin, asset_id: AssetId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let auction = Self::auction(asset_id).ok_or(Error::<T>::NoAuction)?;
+			ensure!(auction.seller == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(auction.highest_bid.is_none() && auction.sealed_bids == 0, Error::<T>::HasBids);
+			<Auctions<T>>::remove(asset_id);
+			// Free the slot of the auction in its end block
+			<AuctionEnds<T>>::mutate(auction.end, |ends| ends.retain(|&id| id != asset_id));
+			Self::restore_status_of(asset_id);
+			Self::deposit_event(RawEvent::AuctionCancelled(asset_id, sender));
+			Ok(())
+		}
+		/// Fractionalise an asset into `shares` transferable shares, all held by its owner,
+		/// the asset and the assets packed in it are locked until every share is redeemed
+		#[weight = T::WeightInfo::fractionalise()]
+		pub fn fractionalise(origin, asset_id: AssetId, shares: u64) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			ensure!(!ShareSupply::contains_key(asset_id), Error::<T>::Fractionalised);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(!<Listings<T>>::contains_key(asset_id), Error::<T>::ForSale);
+			ensure!(!<Auctions<T>>::contains_key(asset_id), Error::<T>::InAuction);
+			ensure!(shares > 1, Error::<T>::InvalidShares);
+			Self::ensure_at_rest(&details)?;
+			// Update storage.
+			ShareSupply::insert(asset_id, shares);
+			<Shares<T>>::insert(asset_id, &sender, shares);
+			Self::record(asset_id, ProvenanceAction::Fractionalised(shares), &sender, None);
+			Self::deposit_event(RawEvent::AssetFractionalised(asset_id, sender, shares));
+			Ok(())
+		}
+		/// Transfer shares of a fractionalised asset
+		#[weight = T::WeightInfo::transfer_shares()]
+		pub fn transfer_shares(origin, asset_id: AssetId, to: T::AccountId, shares: u64) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(ShareSupply::contains_key(asset_id), Error::<T>::NotFractionalised);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(shares > 0, Error::<T>::InvalidAmount);
+			let held = Self::shares(asset_id, &sender);
+			ensure!(held >= shares, Error::<T>::InsufficientShares);
+			// Update storage.
+			if sender != to {
+				if held == shares {
+					<Shares<T>>::remove(asset_id, &sender);
+				} else {
+					<Shares<T>>::insert(asset_id, &sender, held - shares);
+				}
+				<Shares<T>>::mutate(asset_id, &to, |held| *held = held.saturating_add(shares));
+			}
+			Self::deposit_event(RawEvent::SharesTransferred(asset_id, sender, to, shares));
+			Ok(())
+		}
+		/// Redeem every share of a fractionalised asset, the caller becomes the sole owner
+		/// of the asset and of the assets packed in it
+		#[weight = T::WeightInfo::redeem(<Module<T>>::max_descendants())]
+		pub fn redeem(origin, asset_id: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let supply = Self::share_supply(asset_id).ok_or(Error::<T>::NotFractionalised)?;
+			ensure!(Self::shares(asset_id, &sender) == supply, Error::<T>::MissingShares);
+			Self::ensure_not_frozen(asset_id)?;
+			let details = Self::live_asset(asset_id)?;
+			// The shares are only burnt once the assets are known to change hands
+			let assets = if details.owner != sender { Self::tree_details(asset_id)? } else { Vec::new() };
+			Self::reserve_deposits(&assets, &sender)?;
+			// Update storage.
+			ShareSupply::remove(asset_id);
+			<Shares<T>>::remove(asset_id, &sender);
+			Self::record(asset_id, ProvenanceAction::Redeemed, &sender, None);
+			let moved = assets.len().saturating_sub(1) as u32;
+			for (id, details) in assets {
+				Self::do_transfer(id, details, &sender);
+			}
+			Self::deposit_event(RawEvent::AssetRedeemed(asset_id, sender));
+			Ok(Some(T::WeightInfo::redeem(moved)).into())
+		}
+		/// Removal of an asset, a tombstone is kept on chain
+		#[weight = T::WeightInfo::remove_asset(T::MaxAssetLength::get())]
+		pub fn remove_asset(origin, asset_id: AssetId, reason: RemovalReason) -> dispatch::DispatchResultWithPostInfo {
 			// Check that the extrinsic was signed and get the signer.
 			let sender = ensure_signed(origin)?;
-			ensure!(asset.len() >= 16, Error::<T>::TooShort); //check minimum length
-			ensure!(asset.len() <= 8192, Error::<T>::TooLong);  // check maximum length
-			// Transfer storage
-			let assetremove=asset.clone();
-			//todo: add control on previous asset/property
-			<Asset<T>>::insert(&sender, assetremove);
-			// Emit an event
-			Self::deposit_event(RawEvent::AssetRemoved(asset, sender));
-			// Return a successful DispatchResult
+			// Splits and merges are recorded by their own calls
+			ensure!(!matches!(reason, RemovalReason::Split | RemovalReason::Merged), Error::<T>::InvalidReason);
+			// Check the property of the asset
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			Self::ensure_unlocked(asset_id)?;
+			match reason.status() {
+				Some(status) => Self::ensure_transition(&details, status)?,
+				None => Self::ensure_at_rest(&details)?,
+			}
+			// Update storage and emit an event
+			let length = details.data.len() as u32;
+			Self::do_remove(asset_id, details, reason);
+			// Return the weight of the data actually removed
+			Ok(Some(T::WeightInfo::remove_asset(length)).into())
+		}
+		/// Split an asset into new assets, e.g. a barrel into bottles, the quantity is conserved
+		#[weight = T::WeightInfo::split_asset(outputs.len() as u32)]
+		pub fn split_asset(origin, asset_id: AssetId, outputs: Vec<AssetInput>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			Self::ensure_holder(&details, &sender)?;
+			Self::ensure_unlocked(asset_id)?;
+			Self::ensure_at_rest(&details)?;
+			ensure!(!outputs.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(outputs.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+			// Validate every output before touching storage
+			let mut deposit = BalanceOf::<T>::zero();
+			let mut quantity: u64 = 0;
+			let mut validated = Vec::with_capacity(outputs.len());
+			for output in outputs {
+				let metadata = Self::validate_asset(&output.data)?;
+				quantity = quantity.checked_add(metadata.quantity).ok_or(Error::<T>::QuantityMismatch)?;
+				let output_deposit = Self::deposit_for(output.data.len(), &metadata);
+				deposit = deposit.saturating_add(output_deposit);
+				validated.push((output.data, metadata, output_deposit));
+			}
+			ensure!(quantity == details.metadata.quantity, Error::<T>::QuantityMismatch);
+			let count = validated.len() as u32;
+			let asset_ids = Self::free_asset_ids(count)?;
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			// Update storage.
+			let producer = Self::producer_of(asset_id);
+			Self::do_remove(asset_id, details, RemovalReason::Split);
+			for (new_asset_id, (data, metadata, output_deposit)) in asset_ids.clone().zip(validated) {
+				Self::insert_asset(new_asset_id, &sender, data, metadata, None, output_deposit, producer.as_ref());
+				Self::record(new_asset_id, ProvenanceAction::SplitFrom(asset_id), &sender, None);
+				Sources::insert(new_asset_id, vec![asset_id]);
+				Self::deposit_event(RawEvent::NewAssetStored(new_asset_id, sender.clone()));
+			}
+			NextAssetId::put(asset_ids.end);
+			Self::deposit_event(RawEvent::AssetSplit(asset_id, asset_ids.start, count, sender));
+			Ok(())
+		}
+		/// Merge assets into a new asset, e.g. lots into a blend, the quantity is conserved
+		#[weight = T::WeightInfo::merge_assets(sources.len() as u32, data.len() as u32)]
+		pub fn merge_assets(origin, sources: Vec<AssetId>, data: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!sources.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(sources.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+			// Validate every source before touching storage
+			let mut quantity: u64 = 0;
+			let mut merged = Vec::with_capacity(sources.len());
+			for (index, &source) in sources.iter().enumerate() {
+				ensure!(!sources[..index].contains(&source), Error::<T>::DuplicateAsset);
+				let details = Self::live_asset(source)?;
+				Self::ensure_holder(&details, &sender)?;
+				Self::ensure_unlocked(source)?;
+				Self::ensure_at_rest(&details)?;
+				quantity = quantity.checked_add(details.metadata.quantity).ok_or(Error::<T>::QuantityMismatch)?;
+				merged.push((source, details));
+			}
+			let metadata = Self::validate_asset(&data)?;
+			ensure!(metadata.quantity == quantity, Error::<T>::QuantityMismatch);
+			let asset_ids = Self::free_asset_ids(1)?;
+			let asset_id = asset_ids.start;
+			let deposit = Self::deposit_for(data.len(), &metadata);
+			T::Currency::reserve(&sender, deposit).map_err(|_| Error::<T>::InsufficientDeposit)?;
+			// The merged asset keeps the producer of its sources if they share one
+			let producer = Self::producer_of(sources[0]).filter(|producer| {
+				sources[1..].iter().all(|&source| Self::producer_of(source).as_ref() == Some(producer))
+			});
+			// Update storage.
+			for (source, details) in merged {
+				Self::do_remove(source, details, RemovalReason::Merged);
+			}
+			Self::insert_asset(asset_id, &sender, data, metadata, None, deposit, producer.as_ref());
+			for &source in sources.iter() {
+				Self::record(asset_id, ProvenanceAction::MergedFrom(source), &sender, None);
+			}
+			Sources::insert(asset_id, &sources);
+			NextAssetId::put(asset_ids.end);
+			Self::deposit_event(RawEvent::NewAssetStored(asset_id, sender.clone()));
+			Self::deposit_event(RawEvent::AssetsMerged(sources, asset_id, sender));
+			Ok(())
+		}
+		/// Hand over the custody of an asset and of the assets packed in it to another custodian,
+		/// the owner is unchanged
+		#[weight = T::WeightInfo::transfer_custody(<Module<T>>::max_descendants())]
+		pub fn transfer_custody(origin, asset_id: AssetId, to: T::AccountId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.custodian == sender, Error::<T>::NotCustodian);
+			ensure!(!<Offers<T>>::contains_key(asset_id), Error::<T>::PendingOffer);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			Self::ensure_not_frozen(asset_id)?;
+			Self::ensure_role(&to, Role::Custodian)?;
+			Self::ensure_transition(&details, AssetStatus::InStorage)?;
+			let moved = Self::do_transfer_custody_tree(asset_id, details, &to)?;
+			Self::deposit_event(RawEvent::CustodyTransferred(asset_id, sender, to));
+			Ok(Some(T::WeightInfo::transfer_custody(moved)).into())
+		}
+		/// Offer the custody of an asset, the receiver has to accept it before it expires,
+		/// optionally once the asset has recently received an attestation of the given kind
+		#[weight = T::WeightInfo::offer_transfer()]
+		pub fn offer_transfer(
+			origin,
+			asset_id: AssetId,
+			to: T::AccountId,
+			attestation: Option<AttestationKind>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut details = Self::live_asset(asset_id)?;
+			ensure!(details.custodian == sender, Error::<T>::NotCustodian);
+			ensure!(!<Offers<T>>::contains_key(asset_id), Error::<T>::PendingOffer);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			Self::ensure_not_frozen(asset_id)?;
+			Self::ensure_role(&to, Role::Custodian)?;
+			Self::ensure_transition(&details, AssetStatus::InTransit)?;
+			Self::set_status(asset_id, &mut details, AssetStatus::InTransit);
+			<Asset<T>>::insert(asset_id, details);
+			let expires = <frame_system::Module<T>>::block_number().saturating_add(T::OfferExpiry::get());
+			<Offers<T>>::insert(asset_id, TransferOffer { from: sender.clone(), to: to.clone(), expires, attestation });
+			<OfferExpiries<T>>::append(expires, asset_id);
+			Self::deposit_event(RawEvent::TransferOffered(asset_id, sender, to, expires));
+			Ok(())
+		}
+		/// Accept the pending custody handover of an asset, with the assets packed in it
+		#[weight = T::WeightInfo::accept_transfer(<Module<T>>::max_descendants())]
+		pub fn accept_transfer(origin, asset_id: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let offer = Self::offer(asset_id).ok_or(Error::<T>::NoOffer)?;
+			ensure!(offer.to == sender, Error::<T>::NotReceiver);
+			Self::ensure_role(&sender, Role::Custodian)?;
+			Self::ensure_not_frozen(asset_id)?;
+			if let Some(kind) = offer.attestation {
+				ensure!(Self::has_recent_attestation(asset_id, kind), Error::<T>::MissingAttestation);
+			}
+			let details = Self::live_asset(asset_id)?;
+			let moved = Self::do_transfer_custody_tree(asset_id, details, &sender)?;
+			<Offers<T>>::remove(asset_id);
+			Self::deposit_event(RawEvent::TransferAccepted(asset_id, offer.from, sender));
+			Ok(Some(T::WeightInfo::accept_transfer(moved)).into())
+		}
+		/// Reject the pending custody handover of an asset
+		#[weight = T::WeightInfo::reject_transfer()]
+		pub fn reject_transfer(origin, asset_id: AssetId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let offer = Self::offer(asset_id).ok_or(Error::<T>::NoOffer)?;
+			ensure!(offer.to == sender, Error::<T>::NotReceiver);
+			Self::ensure_not_frozen(asset_id)?;
+			<Offers<T>>::remove(asset_id);
+			Self::restore_status_of(asset_id);
+			Self::deposit_event(RawEvent::TransferRejected(asset_id, offer.from, sender));
+			Ok(())
+		}
+		/// Cancel a pending custody offer
+		#[weight = T::WeightInfo::cancel_offer()]
+		pub fn cancel_offer(origin, asset_id: AssetId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let offer = Self::offer(asset_id).ok_or(Error::<T>::NoOffer)?;
+			ensure!(offer.from == sender, Error::<T>::NotCustodian);
+			Self::ensure_not_frozen(asset_id)?;
+			<Offers<T>>::remove(asset_id);
+			Self::restore_status_of(asset_id);
+			Self::deposit_event(RawEvent::OfferCancelled(asset_id, sender, offer.to));
+			Ok(())
+		}
+		/// Report an asset and the assets packed in it lost, a pending custody offer is cancelled
+		#[weight = T::WeightInfo::report_lost(<Module<T>>::max_descendants())]
+		pub fn report_lost(origin, asset_id: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.custodian == sender, Error::<T>::NotCustodian);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			let assets = Self::tree_details(asset_id)?;
+			Self::ensure_transition(&details, AssetStatus::Lost)?;
+			// Update storage.
+			if let Some(offer) = <Offers<T>>::take(asset_id) {
+				Self::deposit_event(RawEvent::OfferCancelled(asset_id, sender.clone(), offer.to));
+			}
+			let moved = assets.len() as u32 - 1;
+			for (id, mut details) in assets {
+				Self::set_status(id, &mut details, AssetStatus::Lost);
+				<Asset<T>>::insert(id, details);
+			}
+			Self::record(asset_id, ProvenanceAction::Lost, &sender, None);
+			Ok(Some(T::WeightInfo::report_lost(moved)).into())
+		}
+		/// Report a lost asset found by its last custodian or its owner, the caller becomes the custodian
+		/// of the asset and of the assets packed in it
+		#[weight = T::WeightInfo::report_found(<Module<T>>::max_descendants())]
+		pub fn report_found(origin, asset_id: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.custodian == sender || details.owner == sender, Error::<T>::NotCustodian);
+			ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(details.status == AssetStatus::Lost, Error::<T>::NotLost);
+			let from = details.custodian.clone();
+			let moved = Self::do_transfer_custody_tree(asset_id, details, &sender)?;
+			Self::deposit_event(RawEvent::CustodyTransferred(asset_id, from, sender));
+			Ok(Some(T::WeightInfo::report_found(moved)).into())
+		}
+		/// Attest the condition of an asset, the full report is kept off chain
+		#[weight = T::WeightInfo::attest()]
+		pub fn attest(
+			origin,
+			asset_id: AssetId,
+			kind: AttestationKind,
+			data_hash: H256,
+			score: u8,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_role(&sender, Role::Inspector)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.status != AssetStatus::Lost, Error::<T>::InvalidStateTransition);
+			ensure!(score <= MAX_SCORE, Error::<T>::InvalidScore);
+			let index = AttestationCount::mutate(asset_id, |count| {
+				let index = *count;
+				*count = count.saturating_add(1);
+				index
+			});
+			<Attestations<T>>::insert(asset_id, index, Attestation {
+				kind,
+				inspector: sender.clone(),
+				data_hash,
+				score,
+				block: <frame_system::Module<T>>::block_number(),
+				timestamp: <pallet_timestamp::Module<T>>::get(),
+			});
+			LatestAttestations::insert(asset_id, kind, index);
+			Self::record(asset_id, ProvenanceAction::Inspected, &sender, None);
+			Self::deposit_event(RawEvent::AssetAttested(asset_id, kind, score, sender));
+			Ok(())
+		}
+		/// Set the environment an asset must be stored in until its custody moves, none to stop monitoring it
+		#[weight = T::WeightInfo::set_tolerance()]
+		pub fn set_tolerance(
+			origin,
+			asset_id: AssetId,
+			tolerance: Option<EnvironmentTolerance>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			let location = details.metadata.location;
+			ensure!(!location.is_empty(), Error::<T>::UnknownLocation);
+			let monitored = Tolerances::contains_key(asset_id);
+			match tolerance {
+				Some(tolerance) => {
+					ensure!(tolerance.is_valid(), Error::<T>::InvalidTolerance);
+					if !monitored {
+						ensure!(
+							Self::monitored_count(&location) < T::MaxMonitoredAssets::get(),
+							Error::<T>::TooManyMonitoredAssets
+						);
+						MonitoredAssets::insert(&location, asset_id, ());
+						MonitoredCount::mutate(&location, |count| *count = count.saturating_add(1));
+					}
+					Tolerances::insert(asset_id, tolerance);
+				}
+				None => {
+					ensure!(monitored, Error::<T>::NotMonitored);
+					Self::stop_monitoring(asset_id, &location);
+				}
+			}
+			Self::deposit_event(RawEvent::ToleranceSet(asset_id, tolerance));
+			Ok(())
+		}
+		/// Store the environment readings reported by the offchain worker of a custodian,
+		/// the signature of the payload is checked by `validate_unsigned`
+		#[weight = T::WeightInfo::submit_environment_unsigned(
+			payload.readings.len() as u32,
+			(payload.readings.len() as u32).saturating_mul(T::MaxMonitoredAssets::get()),
+		)]
+		pub fn submit_environment_unsigned(
+			origin,
+			payload: TelemetryPayload<T::Public, T::BlockNumber>,
+			_signature: T::Signature,
+		) -> dispatch::DispatchResult {
+			ensure_none(origin)?;
+			let reporter = payload.public.clone().into_account();
+			Self::ensure_telemetry(&reporter, &payload)?;
+			<LastTelemetry<T>>::insert(&reporter, payload.block_number);
+			let now = <frame_system::Module<T>>::block_number();
+			let count = payload.readings.len() as u32;
+			for reading in payload.readings {
+				let mut monitored: Vec<AssetId> = MonitoredAssets::iter_prefix(&reading.location)
+					.map(|(asset_id, _)| asset_id)
+					.collect();
+				monitored.sort_unstable();
+				for asset_id in monitored {
+					// Only the custodian of an asset reports the environment it is stored in
+					if Self::asset(asset_id).map_or(true, |details| details.custodian != reporter) {
+						continue;
+					}
+					match Self::tolerance(asset_id) {
+						Some(tolerance) if !tolerance.covers(&reading) => {
+							Self::record(asset_id, ProvenanceAction::EnvironmentBreach, &reporter, None);
+							Self::suspend_policies(asset_id, SuspensionReason::EnvironmentBreach);
+							Self::deposit_event(RawEvent::EnvironmentBreach(asset_id, reading.clone()));
+						}
+						_ => {}
+					}
+				}
+				<LatestReadings<T>>::insert(&reading.location, (now, reading));
+			}
+			Self::deposit_event(RawEvent::EnvironmentReported(reporter, count));
+			Ok(())
+		}
+		/// Register the x25519 public key used to wrap the keys of confidential metadata for the caller
+		#[weight = T::WeightInfo::register_encryption_key()]
+		pub fn register_encryption_key(origin, key: EncryptionKey) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			<EncryptionKeys<T>>::insert(&sender, key);
+			Self::deposit_event(RawEvent::EncryptionKeyRegistered(sender));
+			Ok(())
+		}
+		/// Replace the confidential metadata of an asset, with its key wrapped for each recipient
+		#[weight = T::WeightInfo::set_confidential_metadata(payload.len() as u32, wrapped_keys.len() as u32)]
+		pub fn set_confidential_metadata(
+			origin,
+			asset_id: AssetId,
+			payload: Vec<u8>,
+			wrapped_keys: Vec<(T::AccountId, Vec<u8>)>,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			ensure!(!payload.is_empty(), Error::<T>::TooShort);
+			ensure!(payload.len() <= T::MaxAssetLength::get() as usize, Error::<T>::TooLong);
+			ensure!(wrapped_keys.len() <= T::MaxRecipients::get() as usize, Error::<T>::TooManyRecipients);
+			for (index, (recipient, wrapped_key)) in wrapped_keys.iter().enumerate() {
+				ensure!(
+					!wrapped_keys[..index].iter().any(|(other, _)| other == recipient),
+					Error::<T>::DuplicateRecipient
+				);
+				Self::ensure_wrapped_key(recipient, wrapped_key)?;
+			}
+			let recipients = wrapped_keys.len() as u32;
+			let previous = Self::confidential(asset_id).map(|confidential| confidential.deposit);
+			let confidential = ConfidentialMetadata { payload, wrapped_keys, deposit: previous.unwrap_or_default() };
+			Self::update_confidential(asset_id, details, confidential)?;
+			Self::deposit_event(RawEvent::ConfidentialMetadataSet(asset_id, recipients));
+			Ok(())
+		}
+		/// Disclose the confidential metadata of an asset to an account, or replace its wrapped key
+		#[weight = T::WeightInfo::grant_access(T::MaxRecipients::get())]
+		pub fn grant_access(origin, asset_id: AssetId, account: T::AccountId, wrapped_key: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			let mut confidential = Self::confidential(asset_id).ok_or(Error::<T>::NoConfidentialMetadata)?;
+			Self::ensure_wrapped_key(&account, &wrapped_key)?;
+			match confidential.wrapped_keys.iter_mut().find(|(recipient, _)| *recipient == account) {
+				Some((_, key)) => *key = wrapped_key,
+				None => {
+					ensure!(
+						confidential.wrapped_keys.len() < T::MaxRecipients::get() as usize,
+						Error::<T>::TooManyRecipients
+					);
+					confidential.wrapped_keys.push((account.clone(), wrapped_key));
+				}
+			}
+			Self::update_confidential(asset_id, details, confidential)?;
+			Self::deposit_event(RawEvent::AccessGranted(asset_id, account));
+			Ok(())
+		}
+		/// Remove the wrapped key of an account, the metadata should be encrypted again with a new key
+		/// if the account may have read it already
+		#[weight = T::WeightInfo::revoke_access(T::MaxRecipients::get())]
+		pub fn revoke_access(origin, asset_id: AssetId, account: T::AccountId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(details.owner == sender, Error::<T>::NotOwner);
+			Self::ensure_not_frozen(asset_id)?;
+			let mut confidential = Self::confidential(asset_id).ok_or(Error::<T>::NoConfidentialMetadata)?;
+			let index = confidential.wrapped_keys.iter()
+				.position(|(recipient, _)| *recipient == account)
+				.ok_or(Error::<T>::NoAccess)?;
+			confidential.wrapped_keys.remove(index);
+			Self::update_confidential(asset_id, details, confidential)?;
+			Self::deposit_event(RawEvent::AccessRevoked(asset_id, account));
+			Ok(())
+		}
+		/// Pack assets in a parent asset, they move with it until they are unpacked
+		#[weight = T::WeightInfo::pack(<Module<T>>::max_descendants())]
+		pub fn pack(origin, parent: AssetId, children: Vec<AssetId>) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(parent)?;
+			Self::ensure_holder(&details, &sender)?;
+			ensure!(!<Offers<T>>::contains_key(parent), Error::<T>::PendingOffer);
+			ensure!(!<Auctions<T>>::contains_key(parent), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(parent), Error::<T>::Fractionalised);
+			Self::ensure_not_frozen(parent)?;
+			Self::ensure_at_rest(&details)?;
+			let ancestors = Self::ancestors(parent);
+			Self::ensure_ancestors_unlocked(&ancestors)?;
+			ensure!(!children.is_empty(), Error::<T>::NothingToPack);
+			let packed = Self::children_of(parent);
+			ensure!(
+				packed.len().saturating_add(children.len()) <= T::MaxChildren::get() as usize,
+				Error::<T>::TooManyChildren
+			);
+			// Check every child before touching storage
+			let mut moved: u32 = 0;
+			for (index, &child) in children.iter().enumerate() {
+				ensure!(!children[..index].contains(&child), Error::<T>::DuplicateAsset);
+				ensure!(child != parent && !ancestors.contains(&child), Error::<T>::CyclicPacking);
+				let details = Self::live_asset(child)?;
+				Self::ensure_holder(&details, &sender)?;
+				ensure!(Self::parent_of(child).is_none(), Error::<T>::AssetPacked);
+				ensure!(!<Offers<T>>::contains_key(child), Error::<T>::PendingOffer);
+				ensure!(!<Auctions<T>>::contains_key(child), Error::<T>::InAuction);
+				ensure!(!ShareSupply::contains_key(child), Error::<T>::Fractionalised);
+				Self::ensure_not_frozen(child)?;
+				Self::ensure_at_rest(&details)?;
+				let descendants = Self::descendants_by_level(child);
+				let height = descendants.last().map_or(0, |&(_, level)| level);
+				ensure!(
+					(ancestors.len() as u32).saturating_add(1).saturating_add(height) <= T::MaxNestingDepth::get(),
+					Error::<T>::TooDeep
+				);
+				moved = moved.saturating_add(1).saturating_add(descendants.len() as u32);
+			}
+			// Update storage.
+			for &child in children.iter() {
+				Parent::insert(child, parent);
+			}
+			Children::mutate(parent, |packed| packed.extend_from_slice(&children));
+			Self::deposit_event(RawEvent::AssetsPacked(parent, children, sender));
+			Ok(Some(T::WeightInfo::pack(moved)).into())
+		}
+		/// Unpack the assets directly packed in a parent asset
+		#[weight = T::WeightInfo::unpack(T::MaxChildren::get())]
+		pub fn unpack(origin, parent: AssetId) -> dispatch::DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let details = Self::live_asset(parent)?;
+			Self::ensure_holder(&details, &sender)?;
+			ensure!(!<Auctions<T>>::contains_key(parent), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(parent), Error::<T>::Fractionalised);
+			Self::ensure_not_frozen(parent)?;
+			Self::ensure_at_rest(&details)?;
+			Self::ensure_ancestors_unlocked(&Self::ancestors(parent))?;
+			let children = Self::children_of(parent);
+			ensure!(!children.is_empty(), Error::<T>::NothingToUnpack);
+			Children::remove(parent);
+			for child in children.iter() {
+				Parent::remove(child);
+			}
+			let unpacked = children.len() as u32;
+			Self::deposit_event(RawEvent::AssetsUnpacked(parent, children, sender));
+			Ok(Some(T::WeightInfo::unpack(unpacked)).into())
+		}
+		/// Issue an insurance policy on assets of `holder`, the coverage is reserved from the insurer
+		/// until the policy expires and the assets are covered once the holder pays a premium
+		#[weight = T::WeightInfo::issue_policy(assets.len() as u32)]
+		pub fn issue_policy(
+			origin,
+			holder: T::AccountId,
+			assets: Vec<AssetId>,
+			coverage: BalanceOf<T>,
+			premium: PremiumScheduleOf<T>,
+			expires: T::BlockNumber,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_role(&sender, Role::Insurer)?;
+			ensure!(!assets.is_empty(), Error::<T>::EmptyBatch);
+			ensure!(assets.len() <= T::MaxBatchSize::get() as usize, Error::<T>::BatchTooLarge);
+			ensure!(!coverage.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(!premium.period.is_zero(), Error::<T>::InvalidPremium);
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(expires > now, Error::<T>::InvalidExpiry);
+			ensure!(
+				<PolicyExpiries<T>>::decode_len(expires).unwrap_or(0) < T::MaxPolicyExpiries::get() as usize,
+				Error::<T>::TooManyExpiries
+			);
+			for (index, &asset_id) in assets.iter().enumerate() {
+				ensure!(!assets[..index].contains(&asset_id), Error::<T>::DuplicateAsset);
+				ensure!(Self::live_asset(asset_id)?.owner == holder, Error::<T>::NotOwner);
+				ensure!(
+					Self::policies_of(asset_id).len() < T::MaxPolicies::get() as usize,
+					Error::<T>::TooManyPolicies
+				);
+			}
+			let policy_id = Self::next_policy_id();
+			let next_policy_id = policy_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			T::Currency::reserve(&sender, coverage).map_err(|_| Error::<T>::InsufficientCollateral)?;
+			// Update storage.
+			for &asset_id in assets.iter() {
+				AssetPolicies::append(asset_id, policy_id);
+			}
+			<Policies<T>>::insert(policy_id, Policy {
+				insurer: sender.clone(),
+				holder: holder.clone(),
+				assets,
+				coverage,
+				premium,
+				paid_until: now,
+				expires,
+				status: PolicyStatus::Active,
+				paid_out: Zero::zero(),
+			});
+			<PolicyExpiries<T>>::append(expires, policy_id);
+			NextPolicyId::put(next_policy_id);
+			Self::deposit_event(RawEvent::PolicyIssued(policy_id, sender, holder));
+			Ok(())
+		}
+		/// Pay the next premium of a policy to its insurer, the coverage is extended by one period
+		#[weight = T::WeightInfo::pay_premium()]
+		pub fn pay_premium(origin, policy_id: PolicyId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut policy = Self::policy(policy_id).ok_or(Error::<T>::UnknownPolicy)?;
+			ensure!(policy.holder == sender, Error::<T>::NotHolder);
+			ensure!(policy.status != PolicyStatus::Expired, Error::<T>::PolicyExpired);
+			ensure!(policy.paid_until < policy.expires, Error::<T>::PremiumsPaid);
+			T::Currency::transfer(&sender, &policy.insurer, policy.premium.amount, ExistenceRequirement::KeepAlive)?;
+			// A lapsed coverage starts again from the current block
+			let now = <frame_system::Module<T>>::block_number();
+			policy.paid_until = policy.paid_until.max(now).saturating_add(policy.premium.period).min(policy.expires);
+			let (amount, paid_until) = (policy.premium.amount, policy.paid_until);
+			<Policies<T>>::insert(policy_id, policy);
+			Self::deposit_event(RawEvent::PremiumPaid(policy_id, amount, paid_until));
+			Ok(())
+		}
+		/// Reinstate the coverage of a suspended policy, the owner of its remaining assets becomes its holder
+		#[weight = T::WeightInfo::reinstate_policy(T::MaxBatchSize::get())]
+		pub fn reinstate_policy(origin, policy_id: PolicyId) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut policy = Self::policy(policy_id).ok_or(Error::<T>::UnknownPolicy)?;
+			ensure!(policy.insurer == sender, Error::<T>::NotInsurer);
+			ensure!(matches!(policy.status, PolicyStatus::Suspended(_)), Error::<T>::NotSuspended);
+			let mut owners = policy.assets.iter()
+				.filter_map(|&asset_id| Self::asset(asset_id))
+				.map(|details| details.owner);
+			let holder = owners.next().ok_or(Error::<T>::AssetRemoved)?;
+			ensure!(owners.all(|owner| owner == holder), Error::<T>::MultipleOwners);
+			policy.holder = holder.clone();
+			policy.status = PolicyStatus::Active;
+			<Policies<T>>::insert(policy_id, policy);
+			Self::deposit_event(RawEvent::PolicyReinstated(policy_id, holder));
+			Ok(())
+		}
+		/// File a claim on a policy, its coverage must be active and paid for the current block
+		#[weight = T::WeightInfo::file_claim()]
+		pub fn file_claim(origin, policy_id: PolicyId, amount: BalanceOf<T>, evidence: H256) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let policy = Self::policy(policy_id).ok_or(Error::<T>::UnknownPolicy)?;
+			ensure!(policy.holder == sender, Error::<T>::NotHolder);
+			match policy.status {
+				PolicyStatus::Active => {}
+				PolicyStatus::Suspended(_) => return Err(Error::<T>::CoverageSuspended.into()),
+				PolicyStatus::Expired => return Err(Error::<T>::PolicyExpired.into()),
+			}
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now < policy.paid_until, Error::<T>::PremiumDue);
+			ensure!(!<Claims<T>>::contains_key(policy_id), Error::<T>::PendingClaim);
+			ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+			ensure!(amount <= policy.coverage.saturating_sub(policy.paid_out), Error::<T>::CoverageExceeded);
+			<Claims<T>>::insert(policy_id, Claim { claimant: sender.clone(), amount, evidence, filed: now });
+			Self::deposit_event(RawEvent::ClaimFiled(policy_id, sender, amount));
+			Ok(())
+		}
+		/// Settle the pending claim on a policy, the payout is taken from the reserved coverage
+		#[weight = T::WeightInfo::settle_claim()]
+		pub fn settle_claim(origin, policy_id: PolicyId, payout: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let mut policy = Self::policy(policy_id).ok_or(Error::<T>::UnknownPolicy)?;
+			ensure!(policy.insurer == sender, Error::<T>::NotInsurer);
+			let claim = Self::claim(policy_id).ok_or(Error::<T>::NoClaim)?;
+			ensure!(payout <= claim.amount, Error::<T>::PayoutTooHigh);
+			let missing = T::Currency::repatriate_reserved(&sender, &claim.claimant, payout, BalanceStatus::Free)?;
+			let paid = payout.saturating_sub(missing);
+			// The coverage of an expired policy has been released but the amount of its pending claim
+			if policy.status == PolicyStatus::Expired {
+				T::Currency::unreserve(&sender, claim.amount.saturating_sub(payout));
+			}
+			policy.paid_out = policy.paid_out.saturating_add(paid);
+			<Policies<T>>::insert(policy_id, policy);
+			<Claims<T>>::remove(policy_id);
+			Self::deposit_event(RawEvent::ClaimSettled(policy_id, claim.claimant, paid));
+			Ok(())
+		}
+		/// Grant a role to an account
+		#[weight = T::WeightInfo::grant_role()]
+		pub fn grant_role(origin, who: T::AccountId, role: Role) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(!Self::has_role(&who, role), Error::<T>::RoleAlreadyGranted);
+			<Roles<T>>::insert(&who, role, true);
+			Self::deposit_event(RawEvent::RoleGranted(who, role));
+			Ok(())
+		}
+		/// Revoke a role from an account
+		#[weight = T::WeightInfo::revoke_role()]
+		pub fn revoke_role(origin, who: T::AccountId, role: Role) -> dispatch::DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(Self::has_role(&who, role), Error::<T>::RoleNotGranted);
+			<Roles<T>>::remove(&who, role);
+			Self::deposit_event(RawEvent::RoleRevoked(who, role));
+			Ok(())
+		}
+		/// Freeze an asset, its owner and custodian can't move it, nor the asset it is packed in,
+		/// until it is thawed
+		#[weight = T::WeightInfo::freeze_asset()]
+		pub fn freeze_asset(origin, asset_id: AssetId, reason: FreezeReason) -> dispatch::DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let details = Self::live_asset(asset_id)?;
+			ensure!(!Frozen::contains_key(asset_id), Error::<T>::Frozen);
+			Frozen::insert(asset_id, reason);
+			Self::unindex_recallable(asset_id, &details.metadata);
+			Self::deposit_event(RawEvent::AssetFrozen(asset_id, reason));
+			Ok(())
+		}
+		/// Thaw a frozen asset
+		#[weight = T::WeightInfo::thaw_asset()]
+		pub fn thaw_asset(origin, asset_id: AssetId) -> dispatch::DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(Frozen::contains_key(asset_id), Error::<T>::NotFrozen);
+			Frozen::remove(asset_id);
+			if let Some(details) = Self::asset(asset_id) {
+				Self::index_recallable(asset_id, &details.metadata);
+			}
+			Self::deposit_event(RawEvent::AssetThawed(asset_id));
 			Ok(())
 		}
+		/// Freeze the assets of a producer, or of one of its lots, as named in the asset data,
+		/// at most `MaxRecall` assets are frozen by a call, the recall is repeated until
+		/// `NothingToRecall` for larger lots
+		#[weight = T::WeightInfo::recall(T::MaxRecall::get())]
+		pub fn recall(
+			origin,
+			producer: Vec<u8>,
+			lot: Option<Vec<u8>>,
+			reason: FreezeReason,
+		) -> dispatch::DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			// The frozen assets have left the indices, no more than `MaxRecall` entries are read
+			let assets: Vec<AssetId> = match &lot {
+				Some(lot) => AssetsByLot::iter_prefix((producer.clone(), lot.clone()))
+					.map(|(asset_id, _)| asset_id)
+					.take(T::MaxRecall::get() as usize)
+					.collect(),
+				None => AssetsByProducer::iter_prefix(&producer)
+					.map(|(asset_id, _)| asset_id)
+					.take(T::MaxRecall::get() as usize)
+					.collect(),
+			};
+			ensure!(!assets.is_empty(), Error::<T>::NothingToRecall);
+			for &asset_id in assets.iter() {
+				Frozen::insert(asset_id, reason);
+				if let Some(details) = Self::asset(asset_id) {
+					Self::unindex_recallable(asset_id, &details.metadata);
+				}
+				Self::deposit_event(RawEvent::AssetFrozen(asset_id, reason));
+			}
+			let recalled = assets.len() as u32;
+			Self::deposit_event(RawEvent::AssetsRecalled(producer, lot, recalled));
+			Ok(Some(T::WeightInfo::recall(recalled)).into())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// Ids of the assets owned by `who`, in ascending order
+	pub fn assets_of(who: &T::AccountId) -> Vec<AssetId> {
+		let mut assets: Vec<AssetId> = <AssetsByOwner<T>>::iter_prefix(who).map(|(asset_id, _)| asset_id).collect();
+		assets.sort_unstable();
+		assets
+	}
+
+	/// Ids of the assets in custody of `who`, in ascending order
+	pub fn assets_in_custody(who: &T::AccountId) -> Vec<AssetId> {
+		let mut assets: Vec<AssetId> = <AssetsByCustodian<T>>::iter_prefix(who).map(|(asset_id, _)| asset_id).collect();
+		assets.sort_unstable();
+		assets
+	}
+
+	/// Lifecycle status of an asset, including the final status of the consumed and destroyed assets
+	pub fn status_of(asset_id: AssetId) -> Option<AssetStatus> {
+		match Self::asset(asset_id) {
+			Some(details) => Some(details.status),
+			None => Self::tombstone(asset_id).and_then(|tombstone| tombstone.reason.status()),
+		}
+	}
+
+	/// Holders of the shares of a fractionalised asset, with the number of shares they hold
+	pub fn share_holders(asset_id: AssetId) -> Vec<(T::AccountId, u64)> {
+		<Shares<T>>::iter_prefix(asset_id).collect()
+	}
+
+	/// Page of the provenance log of an asset, oldest entries first
+	pub fn history(asset_id: AssetId, page: u32, page_size: u32) -> Vec<ProvenanceEntryOf<T>> {
+		let start = page.saturating_mul(page_size);
+		let end = start.saturating_add(page_size).min(Self::history_length(asset_id));
+		(start..end).filter_map(|index| <History<T>>::get(asset_id, index)).collect()
+	}
+
+	/// Page of the condition reports of an asset, oldest first
+	pub fn attestations(asset_id: AssetId, page: u32, page_size: u32) -> Vec<AttestationOf<T>> {
+		let start = page.saturating_mul(page_size);
+		let end = start.saturating_add(page_size).min(Self::attestation_count(asset_id));
+		(start..end).filter_map(|index| <Attestations<T>>::get(asset_id, index)).collect()
+	}
+
+	/// Latest condition report of a given kind
+	pub fn latest_attestation(asset_id: AssetId, kind: AttestationKind) -> Option<AttestationOf<T>> {
+		LatestAttestations::get(asset_id, kind).and_then(|index| <Attestations<T>>::get(asset_id, index))
+	}
+
+	/// Latest condition report of each kind, in the order of `AttestationKind::ALL`
+	pub fn latest_attestations(asset_id: AssetId) -> Vec<AttestationOf<T>> {
+		AttestationKind::ALL.iter().filter_map(|&kind| Self::latest_attestation(asset_id, kind)).collect()
+	}
+
+	/// Check that an asset has received an attestation of a given kind within `AttestationValidity`
+	fn has_recent_attestation(asset_id: AssetId, kind: AttestationKind) -> bool {
+		let now = <frame_system::Module<T>>::block_number();
+		Self::latest_attestation(asset_id, kind)
+			.map_or(false, |attestation| attestation.block.saturating_add(T::AttestationValidity::get()) >= now)
+	}
+
+	/// Poll the telemetry endpoint every `TelemetryInterval` blocks and submit the aggregated readings,
+	/// signed by a custodian key of the local keystore
+	fn report_environment(block_number: T::BlockNumber) -> Result<(), &'static str> {
+		let interval = T::TelemetryInterval::get();
+		if interval.is_zero() || !(block_number % interval).is_zero() {
+			return Ok(());
+		}
+		// Nodes without an endpoint don't report any reading
+		let endpoint = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, TELEMETRY_ENDPOINT_KEY) {
+			Some(endpoint) => endpoint,
+			None => return Ok(()),
+		};
+		let endpoint = sp_std::str::from_utf8(&endpoint).map_err(|_| "invalid telemetry endpoint")?;
+		let samples = Self::fetch_samples(endpoint).map_err(|_| "telemetry endpoint unavailable")?;
+		let mut readings = Self::aggregate(samples);
+		if readings.is_empty() {
+			return Ok(());
+		}
+		readings.truncate(T::MaxReadings::get() as usize);
+		let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+			.send_unsigned_transaction(
+				|account| TelemetryPayload {
+					block_number,
+					readings: readings.clone(),
+					public: account.public.clone(),
+				},
+				|payload, signature| Call::submit_environment_unsigned(payload, signature),
+			)
+			.ok_or("no custodian key in the keystore")?;
+		result.map_err(|_| "telemetry submission failed")
+	}
+
+	/// Fetch the samples of the telemetry endpoint
+	fn fetch_samples(endpoint: &str) -> Result<Vec<SensorSample>, http::Error> {
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(TELEMETRY_TIMEOUT));
+		let pending = http::Request::get(endpoint)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+		if response.code != 200 {
+			return Err(http::Error::Unknown);
+		}
+		let body = response.body().collect::<Vec<u8>>();
+		serde_json::from_slice(&body).map_err(|_| http::Error::Unknown)
+	}
+
+	/// Aggregate samples into a reading per storage location, ordered by location
+	fn aggregate(samples: Vec<SensorSample>) -> Vec<EnvironmentReading> {
+		let mut readings = BTreeMap::<Vec<u8>, EnvironmentReading>::new();
+		for sample in samples.into_iter().filter(|sample| !sample.location.is_empty()) {
+			let location = sample.location.into_bytes();
+			let reading = readings.entry(location.clone()).or_insert_with(|| EnvironmentReading {
+				location,
+				min_temperature: sample.temperature,
+				max_temperature: sample.temperature,
+				min_humidity: sample.humidity,
+				max_humidity: sample.humidity,
+				samples: 0,
+			});
+			reading.min_temperature = reading.min_temperature.min(sample.temperature);
+			reading.max_temperature = reading.max_temperature.max(sample.temperature);
+			reading.min_humidity = reading.min_humidity.min(sample.humidity);
+			reading.max_humidity = reading.max_humidity.max(sample.humidity);
+			reading.samples = reading.samples.saturating_add(1);
+		}
+		readings.into_iter().map(|(_, reading)| reading).collect()
+	}
+
+	/// Check that `reporter` may submit the readings of a telemetry payload
+	fn ensure_telemetry(
+		reporter: &T::AccountId,
+		payload: &TelemetryPayload<T::Public, T::BlockNumber>,
+	) -> Result<(), Error<T>> {
+		Self::ensure_role(reporter, Role::Custodian)?;
+		ensure!(!payload.readings.is_empty(), Error::<T>::EmptyBatch);
+		ensure!(payload.readings.len() <= T::MaxReadings::get() as usize, Error::<T>::TooManyReadings);
+		ensure!(payload.readings.iter().all(EnvironmentReading::is_valid), Error::<T>::InvalidReading);
+		ensure!(
+			payload.block_number <= <frame_system::Module<T>>::block_number(),
+			Error::<T>::FutureTelemetry
+		);
+		ensure!(
+			Self::last_telemetry(reporter).map_or(true, |last| payload.block_number > last),
+			Error::<T>::StaleTelemetry
+		);
+		Ok(())
+	}
+
+	/// Suspend the active policies covering an asset
+	fn suspend_policies(asset_id: AssetId, reason: SuspensionReason) {
+		for policy_id in Self::policies_of(asset_id) {
+			if let Some(mut policy) = Self::policy(policy_id) {
+				if policy.status == PolicyStatus::Active {
+					policy.status = PolicyStatus::Suspended(reason);
+					<Policies<T>>::insert(policy_id, policy);
+					Self::deposit_event(RawEvent::PolicySuspended(policy_id, reason));
+				}
+			}
+		}
+	}
+
+	/// Expire a policy and release its coverage, but the amount of its pending claim
+	fn expire_policy(policy_id: PolicyId) {
+		let mut policy = match Self::policy(policy_id) {
+			Some(policy) => policy,
+			None => return,
+		};
+		let pending = Self::claim(policy_id).map_or_else(Zero::zero, |claim| claim.amount);
+		let remaining = policy.coverage.saturating_sub(policy.paid_out).saturating_sub(pending);
+		T::Currency::unreserve(&policy.insurer, remaining);
+		for &asset_id in policy.assets.iter() {
+			let mut policies = Self::policies_of(asset_id);
+			policies.retain(|&id| id != policy_id);
+			if policies.is_empty() {
+				AssetPolicies::remove(asset_id);
+			} else {
+				AssetPolicies::insert(asset_id, policies);
+			}
+		}
+		policy.status = PolicyStatus::Expired;
+		<Policies<T>>::insert(policy_id, policy);
+		Self::deposit_event(RawEvent::PolicyExpired(policy_id));
+	}
+
+	/// Remove the environment tolerance of an asset
+	fn stop_monitoring(asset_id: AssetId, location: &[u8]) {
+		Tolerances::remove(asset_id);
+		MonitoredAssets::remove(location, asset_id);
+		MonitoredCount::mutate(location, |count| *count = count.saturating_sub(1));
+	}
+
+	/// Containment tree of an asset
+	pub fn tree(asset_id: AssetId) -> Option<AssetTree> {
+		Self::asset(asset_id)?;
+		Some(Self::subtree(asset_id))
+	}
+
+	fn subtree(asset_id: AssetId) -> AssetTree {
+		AssetTree {
+			id: asset_id,
+			children: Self::children_of(asset_id).into_iter().map(Self::subtree).collect(),
+		}
+	}
+
+	/// Assets in which an asset is packed, from its direct parent to the top-level asset
+	pub fn ancestors(asset_id: AssetId) -> Vec<AssetId> {
+		let mut ancestors = Vec::new();
+		let mut current = asset_id;
+		while let Some(parent) = Self::parent_of(current) {
+			ancestors.push(parent);
+			current = parent;
+		}
+		ancestors
+	}
+
+	/// Assets packed in an asset, directly or not, breadth first
+	pub fn descendants(asset_id: AssetId) -> Vec<AssetId> {
+		Self::descendants_by_level(asset_id).into_iter().map(|(descendant, _)| descendant).collect()
+	}
+
+	/// Assets packed in an asset, breadth first, with their number of levels below it
+	fn descendants_by_level(asset_id: AssetId) -> Vec<(AssetId, u32)> {
+		let mut descendants: Vec<(AssetId, u32)> = Self::children_of(asset_id)
+			.into_iter()
+			.map(|child| (child, 1))
+			.collect();
+		let mut index = 0;
+		while index < descendants.len() {
+			let (parent, level) = descendants[index];
+			descendants.extend(Self::children_of(parent).into_iter().map(|child| (child, level + 1)));
+			index += 1;
+		}
+		descendants
+	}
+
+	/// Assets from which an asset derives, directly or not, breadth first
+	pub fn lineage(asset_id: AssetId) -> Vec<AssetId> {
+		let mut lineage = Self::sources_of(asset_id);
+		let mut index = 0;
+		while index < lineage.len() {
+			for source in Self::sources_of(lineage[index]) {
+				if !lineage.contains(&source) {
+					lineage.push(source);
+				}
+			}
+			index += 1;
+		}
+		lineage
+	}
+
+	/// Maximum number of assets that can be packed in an asset, directly or not
+	pub fn max_descendants() -> u32 {
+		let children = T::MaxChildren::get();
+		(0..T::MaxNestingDepth::get())
+			.fold((0u32, 1u32), |(total, level), _| {
+				let level = level.saturating_mul(children);
+				(total.saturating_add(level), level)
+			})
+			.0
+	}
+
+	/// Append an entry to the provenance log of an asset
+	fn record(
+		asset_id: AssetId,
+		action: ProvenanceAction,
+		actor: &T::AccountId,
+		counterparty: Option<&T::AccountId>,
+	) {
+		let index = HistoryLength::mutate(asset_id, |length| {
+			let index = *length;
+			*length = length.saturating_add(1);
+			index
+		});
+		<History<T>>::insert(asset_id, index, ProvenanceEntry {
+			action,
+			actor: actor.clone(),
+			counterparty: counterparty.cloned(),
+			block: <frame_system::Module<T>>::block_number(),
+			timestamp: <pallet_timestamp::Module<T>>::get(),
+		});
+	}
+
+	/// Check that a document is the one of an asset, either anchored or stored on chain
+	pub fn verify_document(asset_id: AssetId, document: &[u8]) -> bool {
+		match Self::asset(asset_id) {
+			Some(AssetDetails { anchor: Some(anchor), .. }) => BlakeTwo256::hash(document) == anchor.digest,
+			Some(details) => details.data == document,
+			None => false,
+		}
+	}
+
+	/// Check the length of the asset data and decode its metadata
+	fn validate_asset(data: &[u8]) -> Result<AssetMetadata, Error<T>> {
+		ensure!(data.len() >= T::MinAssetLength::get() as usize, Error::<T>::TooShort); //check minimum length
+		ensure!(data.len() <= T::MaxAssetLength::get() as usize, Error::<T>::TooLong);  // check maximum length
+		Self::decode_metadata(data)
+	}
+
+	/// Find `count` consecutive free asset ids, `NextAssetId` must be updated once they are used.
+	/// Ids are never reused, `NextAssetId` only grows and fails instead of wrapping.
+	fn free_asset_ids(count: u32) -> Result<Range<AssetId>, Error<T>> {
+		let first_asset_id = Self::next_asset_id();
+		let next_asset_id = first_asset_id.checked_add(count.into()).ok_or(Error::<T>::StorageOverflow)?;
+		Ok(first_asset_id..next_asset_id)
+	}
+
+	/// Store a new asset owned and held by `owner`
+	fn insert_asset(
+		asset_id: AssetId,
+		owner: &T::AccountId,
+		data: Vec<u8>,
+		metadata: AssetMetadata,
+		anchor: Option<DocumentAnchor>,
+		deposit: BalanceOf<T>,
+		producer: Option<&T::AccountId>,
+	) {
+		Self::index_recallable(asset_id, &metadata);
+		<Asset<T>>::insert(asset_id, AssetDetails {
+			owner: owner.clone(),
+			custodian: owner.clone(),
+			data,
+			metadata,
+			anchor,
+			deposit,
+			status: AssetStatus::Registered,
+		});
+		<AssetsByOwner<T>>::insert(owner, asset_id, ());
+		<AssetsByCustodian<T>>::insert(owner, asset_id, ());
+		if let Some(producer) = producer {
+			<Producers<T>>::insert(asset_id, producer);
+		}
+		Self::record(asset_id, ProvenanceAction::Created, owner, None);
+	}
+
+	/// Decode the metadata of an asset from its data (base64 encoding + json)
+	pub fn decode_metadata(data: &[u8]) -> Result<AssetMetadata, Error<T>> {
+		let json = base64::decode(data).map_err(|_| Error::<T>::InvalidBase64)?;
+		let document: AssetDocument = serde_json::from_slice(&json)
+			.map_err(|_| Error::<T>::InvalidJson)?;
+		let required = |field: Option<String>| match field {
+			Some(value) if !value.is_empty() => Ok(value.into_bytes()),
+			_ => Err(Error::<T>::MissingField),
+		};
+		Ok(AssetMetadata {
+			producer: required(document.producer)?,
+			vintage: document.vintage,
+			format: required(document.format)?,
+			lot: required(document.lot)?,
+			location: required(document.location)?,
+			quantity: match document.quantity {
+				Some(0) => return Err(Error::<T>::InvalidQuantity),
+				Some(quantity) => quantity,
+				None => 1,
+			},
+		})
+	}
+
+	/// Move the ownership of an asset to a new owner, who has reserved the storage deposit with `reserve_deposits`
+	fn do_transfer(asset_id: AssetId, mut details: AssetDetailsOf<T>, to: &T::AccountId) {
+		let from = details.owner.clone();
+		// The new owner has reserved its own deposit, the deposit of the previous owner is given back
+		if from != *to {
+			T::Currency::unreserve(&from, details.deposit);
+		}
+		details.owner = to.clone();
+		<AssetsByOwner<T>>::remove(&from, asset_id);
+		<AssetsByOwner<T>>::insert(to, asset_id, ());
+		// The offer of the previous owner no longer stands
+		<Listings<T>>::remove(asset_id);
+		if details.status == AssetStatus::Listed {
+			Self::restore_status(asset_id, &mut details);
+		}
+		<Asset<T>>::insert(asset_id, details);
+		Self::record(asset_id, ProvenanceAction::Transferred, &from, Some(to));
+		// The insurers have to accept the new owner
+		Self::suspend_policies(asset_id, SuspensionReason::Transferred);
+	}
+
+	/// Reserve from a new owner the storage deposits of the assets it receives,
+	/// before any of them is moved with `do_transfer`
+	fn reserve_deposits(assets: &[(AssetId, AssetDetailsOf<T>)], to: &T::AccountId) -> Result<(), Error<T>> {
+		let deposit = assets.iter()
+			.filter(|(_, details)| details.owner != *to)
+			.fold(BalanceOf::<T>::zero(), |deposit, (_, details)| deposit.saturating_add(details.deposit));
+		T::Currency::reserve(to, deposit).map_err(|_| Error::<T>::InsufficientDeposit)
+	}
+
+	/// Pay the price of a sale, the marketplace fee goes to the platform,
+	/// the royalty to the producer of the asset and the rest to the seller
+	fn pay_sale(asset_id: AssetId, seller: &T::AccountId, payment: NegativeImbalanceOf<T>, price: BalanceOf<T>) {
+		let (fee, payment) = payment.split(T::MarketplaceFee::get() * price);
+		T::Currency::resolve_creating(&T::PlatformAccount::get(), fee);
+		let payment = match Self::producer_of(asset_id) {
+			Some(producer) => {
+				let (royalty, payment) = payment.split(T::RoyaltyFee::get() * price);
+				T::Currency::resolve_creating(&producer, royalty);
+				payment
+			}
+			None => payment,
+		};
+		T::Currency::resolve_creating(seller, payment);
+	}
+
+	/// Commitment of a sealed bid, binding the bidder to the amount of its bid
+	pub fn bid_commitment(bidder: &T::AccountId, amount: BalanceOf<T>, salt: &H256) -> H256 {
+		BlakeTwo256::hash_of(&(bidder, amount, salt))
+	}
+
+	/// Close an auction reaching its end, the highest bid buys the asset and the other bids are unreserved
+	fn close_auction(asset_id: AssetId, now: T::BlockNumber) {
+		let auction = match Self::auction(asset_id) {
+			// auctions cancelled in the meantime are gone
+			Some(auction) if auction.end == now => auction,
+			_ => return,
+		};
+		<Auctions<T>>::remove(asset_id);
+		// The asset is listed until it is sold
+		if auction.highest_bid.is_none() {
+			Self::restore_status_of(asset_id);
+		}
+		// Only the amount of the winning sealed bid stays reserved
+		for (bidder, bid) in <SealedBids<T>>::drain_prefix(asset_id) {
+			let kept = match &auction.highest_bid {
+				Some((winner, amount)) if *winner == bidder => *amount,
+				_ => Zero::zero(),
+			};
+			T::Currency::unreserve(&bidder, bid.deposit.saturating_sub(kept));
+		}
+		let winner = match auction.highest_bid {
+			Some((winner, price)) => match Self::tree_details(asset_id)
+				.and_then(|assets| Self::reserve_deposits(&assets, &winner).map(|_| assets))
+			{
+				Ok(assets) => {
+					let (payment, _) = T::Currency::slash_reserved(&winner, price);
+					Self::pay_sale(asset_id, &auction.seller, payment, price);
+					for (id, details) in assets {
+						Self::do_transfer(id, details, &winner);
+					}
+					Self::deposit_event(RawEvent::AssetSold(asset_id, auction.seller, winner.clone(), price));
+					Some(winner)
+				}
+				// The assets are locked during the auction, the bid is given back if they are gone anyway,
+				// have been frozen or if the winner can't afford their storage deposits
+				Err(error) => {
+					debug::warn!("wivsupplychain: auction of asset {} not settled: {:?}", asset_id, error);
+					T::Currency::unreserve(&winner, price);
+					Self::restore_status_of(asset_id);
+					None
+				}
+			},
+			None => None,
+		};
+		Self::deposit_event(RawEvent::AuctionClosed(asset_id, winner));
+	}
+
+	/// Details of an asset and of the assets packed in it
+	fn tree_details(asset_id: AssetId) -> Result<Vec<(AssetId, AssetDetailsOf<T>)>, Error<T>> {
+		Self::ensure_not_frozen(asset_id)?;
+		let mut assets = vec![(asset_id, Self::live_asset(asset_id)?)];
+		for descendant in Self::descendants(asset_id) {
+			assets.push((descendant, Self::live_asset(descendant)?));
+		}
+		Ok(assets)
+	}
+
+	/// Move the custody of an asset to a new custodian
+	fn do_transfer_custody(asset_id: AssetId, mut details: AssetDetailsOf<T>, to: &T::AccountId) {
+		let from = details.custodian.clone();
+		// The tolerance was set for the storage of the previous custodian
+		if Tolerances::contains_key(asset_id) {
+			Self::stop_monitoring(asset_id, &details.metadata.location);
+			Self::deposit_event(RawEvent::ToleranceSet(asset_id, None));
+		}
+		details.custodian = to.clone();
+		Self::set_status(asset_id, &mut details, AssetStatus::InStorage);
+		<Asset<T>>::insert(asset_id, details);
+		<AssetsByCustodian<T>>::remove(&from, asset_id);
+		<AssetsByCustodian<T>>::insert(to, asset_id, ());
+		Self::record(asset_id, ProvenanceAction::Stored, &from, Some(to));
+	}
+
+	/// Move the custody of an asset and of the assets packed in it, return the number of packed assets
+	fn do_transfer_custody_tree(
+		asset_id: AssetId,
+		details: AssetDetailsOf<T>,
+		to: &T::AccountId,
+	) -> Result<u32, Error<T>> {
+		let mut descendants = Vec::new();
+		for descendant in Self::descendants(asset_id) {
+			descendants.push((descendant, Self::live_asset(descendant)?));
+		}
+		// Every asset is read before the first one is moved
+		let moved = descendants.len() as u32;
+		Self::do_transfer_custody(asset_id, details, to);
+		for (descendant, details) in descendants {
+			Self::do_transfer_custody(descendant, details, to);
+		}
+		Ok(moved)
+	}
+
+	/// Index an asset under the producer and the lot named in its data, for recalls
+	fn index_recallable(asset_id: AssetId, metadata: &AssetMetadata) {
+		if !metadata.producer.is_empty() {
+			AssetsByProducer::insert(&metadata.producer, asset_id, ());
+			AssetsByLot::insert((metadata.producer.clone(), metadata.lot.clone()), asset_id, ());
+		}
+	}
+
+	/// Remove an asset from the recall indices, once frozen or removed
+	fn unindex_recallable(asset_id: AssetId, metadata: &AssetMetadata) {
+		AssetsByProducer::remove(&metadata.producer, asset_id);
+		AssetsByLot::remove((metadata.producer.clone(), metadata.lot.clone()), asset_id);
+	}
+
+	/// Remove an asset, give back its storage deposit and keep a tombstone
+	fn do_remove(asset_id: AssetId, details: AssetDetailsOf<T>, reason: RemovalReason) {
+		T::Currency::unreserve(&details.owner, details.deposit);
+		<Asset<T>>::remove(asset_id);
+		<Confidential<T>>::remove(asset_id);
+		<Producers<T>>::remove(asset_id);
+		Self::unindex_recallable(asset_id, &details.metadata);
+		<Listings<T>>::remove(asset_id);
+		if Tolerances::contains_key(asset_id) {
+			Self::stop_monitoring(asset_id, &details.metadata.location);
+		}
+		<AssetsByOwner<T>>::remove(&details.owner, asset_id);
+		<AssetsByCustodian<T>>::remove(&details.custodian, asset_id);
+		<Tombstones<T>>::insert(asset_id, Tombstone {
+			block: <frame_system::Module<T>>::block_number(),
+			removed_by: details.owner.clone(),
+			reason,
+		});
+		Self::record(asset_id, ProvenanceAction::Removed, &details.owner, None);
+		if let Some(status) = reason.status() {
+			Self::deposit_event(RawEvent::StatusChanged(asset_id, status));
+		}
+		Self::deposit_event(RawEvent::AssetRemoved(asset_id, details.owner, reason));
+	}
+
+	/// Check that a key can be wrapped for `recipient`
+	fn ensure_wrapped_key(recipient: &T::AccountId, wrapped_key: &[u8]) -> Result<(), Error<T>> {
+		ensure!(<EncryptionKeys<T>>::contains_key(recipient), Error::<T>::NoEncryptionKey);
+		ensure!(
+			!wrapped_key.is_empty() && wrapped_key.len() <= MAX_WRAPPED_KEY_LENGTH,
+			Error::<T>::InvalidWrappedKey
+		);
+		Ok(())
+	}
+
+	/// Store the confidential metadata of an asset and adjust its deposit to the stored bytes
+	fn update_confidential(
+		asset_id: AssetId,
+		mut details: AssetDetailsOf<T>,
+		mut confidential: ConfidentialMetadataOf<T>,
+	) -> dispatch::DispatchResult {
+		let len = confidential.wrapped_keys.iter()
+			.fold(confidential.payload.len(), |len, (_, key)| len.saturating_add(key.len()));
+		let deposit = T::AssetDepositPerByte::get().saturating_mul((len as u32).into());
+		if deposit > confidential.deposit {
+			T::Currency::reserve(&details.owner, deposit - confidential.deposit)
+				.map_err(|_| Error::<T>::InsufficientDeposit)?;
+		} else {
+			T::Currency::unreserve(&details.owner, confidential.deposit - deposit);
+		}
+		details.deposit = details.deposit.saturating_sub(confidential.deposit).saturating_add(deposit);
+		confidential.deposit = deposit;
+		<Asset<T>>::insert(asset_id, details);
+		<Confidential<T>>::insert(asset_id, confidential);
+		Ok(())
+	}
+
+	/// Check that an asset has no pending custody offer, is neither up for auction nor fractionalised
+	/// and is neither packed nor containing assets
+	fn ensure_unlocked(asset_id: AssetId) -> Result<(), Error<T>> {
+		ensure!(!<Offers<T>>::contains_key(asset_id), Error::<T>::PendingOffer);
+		ensure!(!<Auctions<T>>::contains_key(asset_id), Error::<T>::InAuction);
+		ensure!(!ShareSupply::contains_key(asset_id), Error::<T>::Fractionalised);
+		ensure!(!Frozen::contains_key(asset_id), Error::<T>::Frozen);
+		ensure!(Self::parent_of(asset_id).is_none(), Error::<T>::AssetPacked);
+		ensure!(!Children::contains_key(asset_id), Error::<T>::NotEmpty);
+		Ok(())
+	}
+
+	/// Check that the content of the assets an asset is packed in can change,
+	/// they are neither handed over, listed, shared out nor lost
+	fn ensure_ancestors_unlocked(ancestors: &[AssetId]) -> Result<(), Error<T>> {
+		for &ancestor in ancestors {
+			ensure!(!<Listings<T>>::contains_key(ancestor), Error::<T>::ForSale);
+			ensure!(!<Auctions<T>>::contains_key(ancestor), Error::<T>::InAuction);
+			ensure!(!ShareSupply::contains_key(ancestor), Error::<T>::Fractionalised);
+			ensure!(!<Offers<T>>::contains_key(ancestor), Error::<T>::PendingOffer);
+			Self::ensure_at_rest(&Self::live_asset(ancestor)?)?;
+		}
+		Ok(())
+	}
+
+	/// Check that neither an asset, the assets packed in it nor the assets it is packed in
+	/// are frozen
+	fn ensure_not_frozen(asset_id: AssetId) -> Result<(), Error<T>> {
+		ensure!(!Frozen::contains_key(asset_id), Error::<T>::Frozen);
+		for related in Self::descendants(asset_id).into_iter().chain(Self::ancestors(asset_id)) {
+			ensure!(!Frozen::contains_key(related), Error::<T>::Frozen);
+		}
+		Ok(())
+	}
+
+	/// Check that the lifecycle of an asset allows it to become `status`
+	fn ensure_transition(details: &AssetDetailsOf<T>, status: AssetStatus) -> Result<(), Error<T>> {
+		ensure!(details.status.can_become(status), Error::<T>::InvalidStateTransition);
+		Ok(())
+	}
+
+	/// Check that an asset is neither listed, in transit nor lost
+	fn ensure_at_rest(details: &AssetDetailsOf<T>) -> Result<(), Error<T>> {
+		ensure!(details.status.is_at_rest(), Error::<T>::InvalidStateTransition);
+		Ok(())
+	}
+
+	/// Change the status of an asset, the status of an asset becoming listed or in transit is kept
+	/// until its sale or handover is withdrawn
+	fn set_status(asset_id: AssetId, details: &mut AssetDetailsOf<T>, status: AssetStatus) {
+		match status {
+			AssetStatus::Listed | AssetStatus::InTransit => {
+				if details.status.is_at_rest() {
+					PriorStatus::insert(asset_id, details.status);
+				}
+			}
+			_ => PriorStatus::remove(asset_id),
+		}
+		if details.status != status {
+			details.status = status;
+			Self::deposit_event(RawEvent::StatusChanged(asset_id, status));
+		}
+	}
+
+	/// Give back to a listed asset or an asset in transit the status it had before
+	fn restore_status(asset_id: AssetId, details: &mut AssetDetailsOf<T>) {
+		if let Some(status) = PriorStatus::take(asset_id) {
+			Self::set_status(asset_id, details, status);
+		}
+	}
+
+	/// Give back to a stored asset the status it had before being listed or in transit
+	fn restore_status_of(asset_id: AssetId) {
+		<Asset<T>>::mutate(asset_id, |details| {
+			if let Some(details) = details {
+				Self::restore_status(asset_id, details);
+			}
+		});
+	}
+
+	/// Check that `who` both owns and holds an asset
+	fn ensure_holder(details: &AssetDetailsOf<T>, who: &T::AccountId) -> Result<(), Error<T>> {
+		ensure!(details.owner == *who, Error::<T>::NotOwner);
+		ensure!(details.custodian == *who, Error::<T>::NotCustodian);
+		Ok(())
+	}
+
+	/// Check that `who` has been granted `role`
+	fn ensure_role(who: &T::AccountId, role: Role) -> Result<(), Error<T>> {
+		ensure!(Self::has_role(who, role), Error::<T>::MissingRole);
+		Ok(())
+	}
+
+	/// Storage deposit of an asset with `len` bytes of data, its decoded metadata is stored
+	/// along with the data and is paid for as well
+	pub fn deposit_for(len: usize, metadata: &AssetMetadata) -> BalanceOf<T> {
+		let bytes = len.saturating_add(metadata.encoded_size());
+		T::AssetDepositPerByte::get()
+			.saturating_mul((bytes as u32).into())
+			.saturating_add(T::AssetDepositBase::get())
+	}
+
+	/// Details of an asset that has not been removed
+	fn live_asset(asset_id: AssetId) -> Result<AssetDetailsOf<T>, Error<T>> {
+		match Self::asset(asset_id) {
+			Some(details) => Ok(details),
+			None if <Tombstones<T>>::contains_key(asset_id) => Err(Error::<T>::AssetRemoved),
+			None => Err(Error::<T>::UnknownAsset),
+		}
+	}
+}
+
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+	type Call = Call<T>;
+
+	/// Accept the telemetry submissions signed by a custodian, once per collection block
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		if let Call::submit_environment_unsigned(payload, signature) = call {
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into();
+			}
+			let reporter = payload.public.clone().into_account();
+			match Self::ensure_telemetry(&reporter, payload) {
+				Ok(()) => {}
+				Err(Error::<T>::MissingRole) => return InvalidTransaction::BadProof.into(),
+				Err(Error::<T>::StaleTelemetry) => return InvalidTransaction::Stale.into(),
+				Err(Error::<T>::FutureTelemetry) => return InvalidTransaction::Future.into(),
+				Err(_) => return InvalidTransaction::Call.into(),
+			}
+			ValidTransaction::with_tag_prefix("WivSupplyChainTelemetry")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((reporter, payload.block_number))
+				.longevity(T::TelemetryInterval::get().saturated_into::<u64>())
+				.propagate(true)
+				.build()
+		} else {
+			InvalidTransaction::Call.into()
+		}
 	}
 }
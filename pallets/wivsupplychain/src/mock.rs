@@ -1,8 +1,9 @@
-use crate::{Module, Trait};
+use crate::{Module, Trait, GenesisConfig, Role};
 use sp_core::H256;
 use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, TestXt, UintAuthorityId}, Perbill, Permill,
+	transaction_validity::TransactionPriority,
 };
 use frame_system as system;
 
@@ -43,19 +44,138 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type PalletInfo = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
 }
 
+parameter_types! {
+	pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const MinAssetLength: u32 = 20;
+	pub const MaxAssetLength: u32 = 1024;
+	pub const MaxUriLength: u32 = 64;
+	pub const MaxBatchSize: u32 = 4;
+	pub const MaxRecipients: u32 = 3;
+	pub const MaxNestingDepth: u32 = 2;
+	pub const MaxChildren: u32 = 3;
+	pub const AssetDepositBase: u64 = 10;
+	pub const AssetDepositPerByte: u64 = 1;
+	pub const OfferExpiry: u64 = 10;
+	pub const AttestationValidity: u64 = 20;
+	pub const MaxPolicies: u32 = 2;
+	pub const MaxPolicyExpiries: u32 = 2;
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const RoyaltyFee: Permill = Permill::from_percent(5);
+	pub const PlatformAccount: u64 = 100;
+	pub const MaxAuctionDuration: u64 = 20;
+	pub const RevealPeriod: u64 = 5;
+	pub const MaxSealedBids: u32 = 3;
+	pub const MaxAuctionsPerBlock: u32 = 2;
+	pub const MaxRecall: u32 = 3;
+	pub const TelemetryInterval: u64 = 5;
+	pub const MaxReadings: u32 = 3;
+	pub const MaxMonitoredAssets: u32 = 2;
+	pub const UnsignedPriority: TransactionPriority = 1 << 20;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 50;
+}
+
+impl pallet_balances::Trait for Test {
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = ();
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+pub type Extrinsic = TestXt<crate::Call<Test>, ()>;
+
+impl system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<C> system::offchain::SendTransactionTypes<C> for Test where crate::Call<Test>: From<C> {
+	type OverarchingCall = crate::Call<Test>;
+	type Extrinsic = Extrinsic;
+}
+
+/// Signer of the environment readings, its keys are set with `UintAuthorityId::set_all_keys`
+pub struct TestAuthId;
+
+impl system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
 impl Trait for Test {
 	type Event = ();
+	type AuthorityId = TestAuthId;
+	type TelemetryInterval = TelemetryInterval;
+	type MaxReadings = MaxReadings;
+	type MaxMonitoredAssets = MaxMonitoredAssets;
+	type UnsignedPriority = UnsignedPriority;
+	type AdminOrigin = system::EnsureRoot<u64>;
+	type ForceOrigin = system::EnsureRoot<u64>;
+	type MaxRecall = MaxRecall;
+	type OfferExpiry = OfferExpiry;
+	type AttestationValidity = AttestationValidity;
+	type MaxPolicies = MaxPolicies;
+	type MaxPolicyExpiries = MaxPolicyExpiries;
+	type MarketplaceFee = MarketplaceFee;
+	type RoyaltyFee = RoyaltyFee;
+	type PlatformAccount = PlatformAccount;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type RevealPeriod = RevealPeriod;
+	type MaxSealedBids = MaxSealedBids;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MinAssetLength = MinAssetLength;
+	type MaxAssetLength = MaxAssetLength;
+	type MaxUriLength = MaxUriLength;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxRecipients = MaxRecipients;
+	type MaxNestingDepth = MaxNestingDepth;
+	type MaxChildren = MaxChildren;
+	type Currency = Balances;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerByte = AssetDepositPerByte;
+	type WeightInfo = ();
 }
 
-pub type TemplateModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Timestamp = pallet_timestamp::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type WivSupplyChain = Module<Test>;
 
 // Build genesis storage according to the mock runtime.
+// Account 1 is endowed with 10_000 to register larger trees of assets, accounts 2 to 300 with 1_000,
+// other accounts have no funds.
+// Account 1 is a producer, accounts 2 and 3 are custodians, account 5 is an inspector,
+// account 6 is an insurer.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: (1..=300).map(|who| (who, if who == 1 { 10_000 } else { 1_000 })).collect(),
+	}.assimilate_storage(&mut t).unwrap();
+	GenesisConfig::<Test> {
+		roles: vec![(1, Role::Producer), (2, Role::Custodian), (3, Role::Custodian), (5, Role::Inspector), (6, Role::Insurer)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
 }
@@ -1,23 +1,2080 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{
+	Error, AssetDetails, AssetMetadata, RemovalReason, Tombstone, ProvenanceAction, ProvenanceEntry,
+	Role, TransferOffer, AssetInput, AssetTree, DocumentAnchor, Attestation, AttestationKind,
+	EnvironmentReading, EnvironmentTolerance, TelemetryPayload, TELEMETRY_ENDPOINT_KEY, PolicyStatus,
+	PremiumSchedule, SuspensionReason, AuctionKind, AssetStatus, FreezeReason, mock::*,
+};
+use codec::{Encode, Decode};
+use frame_support::{assert_ok, assert_noop, traits::{OnFinalize, OnInitialize, OffchainWorker}, unsigned::ValidateUnsigned};
+use frame_system::offchain::SignedPayload;
+use sp_core::offchain::{
+	testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt}, OffchainExt, StorageKind, TransactionPoolExt,
+};
+use sp_runtime::{
+	DispatchError,
+	testing::{TestSignature, UintAuthorityId},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+};
+use sp_io::hashing::blake2_256;
+use primitive_types::H256;
+
+/// base64 encoding of a complete asset document
+const ASSET: &[u8] = b"eyJwcm9kdWNlciI6IkNoYXRlYXUgTWFyZ2F1eCIsInZpbnRhZ2UiOjIwMTUsImZvcm1hdCI6Ijc1MG1sIiwibG90IjoiTDE1LTA0MiIsImxvY2F0aW9uIjoiTG9uZG9uIEJvbmQifQ==";
+
+fn metadata() -> AssetMetadata {
+	AssetMetadata {
+		producer: b"Chateau Margaux".to_vec(),
+		vintage: Some(2015),
+		format: b"750ml".to_vec(),
+		lot: b"L15-042".to_vec(),
+		location: b"London Bond".to_vec(),
+		quantity: 1,
+	}
+}
+
+/// Storage deposit of `ASSET`, its decoded metadata is stored as well
+fn asset_deposit() -> u64 {
+	10 + (ASSET.len() + metadata().encoded_size()) as u64
+}
+
+fn encode(json: &str) -> Vec<u8> {
+	base64::encode(json).into_bytes()
+}
+
+#[test]
+fn new_asset_assigns_unique_ids() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		// Both assets are kept for the same owner.
+		let deposit = asset_deposit();
+		let details = AssetDetails {
+			owner: 1,
+			custodian: 1,
+			data: ASSET.to_vec(),
+			metadata: metadata(),
+			anchor: None,
+			deposit,
+			status: AssetStatus::Registered,
+		};
+		assert_eq!(WivSupplyChain::asset(0), Some(details.clone()));
+		assert_eq!(WivSupplyChain::asset(1), Some(details));
+		assert_eq!(WivSupplyChain::next_asset_id(), 2);
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![0, 1]);
+		assert_eq!(WivSupplyChain::assets_in_custody(&1), vec![0, 1]);
+	});
+}
+
+#[test]
+fn new_asset_checks_length() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), b"too short".to_vec()),
+			Error::<Test>::TooShort
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), vec![b'a'; 19]),
+			Error::<Test>::TooShort
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), vec![b'a'; 1025]),
+			Error::<Test>::TooLong
+		);
+	});
+}
+
+#[test]
+fn new_asset_decodes_metadata() {
+	new_test_ext().execute_with(|| {
+		let asset = encode(r#"{"producer":"Krug","format":"1500ml","lot":"K-7","location":"Reims","notes":"NV"}"#);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), asset));
+		// Non-vintage assets have no vintage, unknown fields are ignored.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.metadata), Some(AssetMetadata {
+			producer: b"Krug".to_vec(),
+			vintage: None,
+			format: b"1500ml".to_vec(),
+			lot: b"K-7".to_vec(),
+			location: b"Reims".to_vec(),
+			quantity: 1,
+		}));
+	});
+}
+
+#[test]
+fn new_asset_rejects_malformed_data() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), b"this is not base64 at all!".to_vec()),
+			Error::<Test>::InvalidBase64
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), encode("producer: Krug, lot: K-7")),
+			Error::<Test>::InvalidJson
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), encode(r#"{"producer":"Krug","vintage":"old"}"#)),
+			Error::<Test>::InvalidJson
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), encode(r#"{"producer":"Krug","format":"750ml"}"#)),
+			Error::<Test>::MissingField
+		);
+		assert_noop!(
+			WivSupplyChain::new_asset(
+				Origin::signed(1),
+				encode(r#"{"producer":"","format":"750ml","lot":"K-7","location":"Reims"}"#)
+			),
+			Error::<Test>::MissingField
+		);
+	});
+}
+
+#[test]
+fn new_asset_fails_when_ids_are_exhausted() {
+	new_test_ext().execute_with(|| {
+		crate::NextAssetId::put(u64::max_value());
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+fn batch(count: usize) -> Vec<AssetInput> {
+	vec![AssetInput { data: ASSET.to_vec() }; count]
+}
+
+#[test]
+fn new_assets_batch_stores_every_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_assets_batch(Origin::signed(1), batch(4)));
+		// The batch gets consecutive ids after the existing asset.
+		assert_eq!(WivSupplyChain::next_asset_id(), 5);
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![0, 1, 2, 3, 4]);
+		assert_eq!(WivSupplyChain::assets_in_custody(&1), vec![0, 1, 2, 3, 4]);
+		for asset_id in 1..5 {
+			assert_eq!(WivSupplyChain::asset(asset_id).map(|a| a.metadata), Some(metadata()));
+			assert_eq!(WivSupplyChain::history_length(asset_id), 1);
+		}
+		// Each asset of the batch has its own deposit.
+		assert_eq!(Balances::reserved_balance(1), 5 * asset_deposit());
+	});
+}
+
+#[test]
+fn new_assets_batch_is_atomic() {
+	new_test_ext().execute_with(|| {
+		// A single invalid asset rejects the whole batch.
+		let mut assets = batch(3);
+		assets[1] = AssetInput { data: encode(r#"{"producer":"Krug","format":"750ml"}"#) };
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(1), assets),
+			Error::<Test>::MissingField
+		);
+		let mut assets = batch(3);
+		assets[2] = AssetInput { data: vec![b'a'; 1025] };
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(1), assets),
+			Error::<Test>::TooLong
+		);
+		// The deposit of the whole batch must be available.
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 4, Role::Producer));
+		assert_ok!(WivSupplyChain::new_assets_batch(Origin::signed(4), batch(4)));
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(4), batch(4)),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn new_assets_batch_checks_its_size() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(1), batch(0)),
+			Error::<Test>::EmptyBatch
+		);
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(1), batch(5)),
+			Error::<Test>::BatchTooLarge
+		);
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(2), batch(1)),
+			Error::<Test>::MissingRole
+		);
+		crate::NextAssetId::put(u64::max_value() - 1);
+		assert_noop!(
+			WivSupplyChain::new_assets_batch(Origin::signed(1), batch(2)),
+			Error::<Test>::StorageOverflow
+		);
+	});
+}
+
+#[test]
+fn unknown_asset_id_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WivSupplyChain::transfer_asset(Origin::signed(1), 7, 2),
+			Error::<Test>::UnknownAsset
+		);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 7, RemovalReason::Consumed),
+			Error::<Test>::UnknownAsset
+		);
+	});
+}
+
+#[test]
+fn transfer_asset_moves_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 2));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(2));
+		assert!(WivSupplyChain::assets_of(&1).is_empty());
+		assert_eq!(WivSupplyChain::assets_of(&2), vec![0]);
+		// The new owner can hand it over again.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(2), 0, 3));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(3));
+	});
+}
+
+#[test]
+fn transfer_asset_requires_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::transfer_asset(Origin::signed(2), 0, 2),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn remove_asset_deletes_asset_and_keeps_tombstone() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed));
+		assert_eq!(WivSupplyChain::asset(0), None);
+		assert!(WivSupplyChain::assets_of(&1).is_empty());
+		assert_eq!(
+			WivSupplyChain::tombstone(0),
+			Some(Tombstone { block: 5, removed_by: 1, reason: RemovalReason::Consumed })
+		);
+		// A removed asset can't be used anymore.
+		assert_noop!(
+			WivSupplyChain::transfer_asset(Origin::signed(1), 0, 2),
+			Error::<Test>::AssetRemoved
+		);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Destroyed),
+			Error::<Test>::AssetRemoved
+		);
+	});
+}
+
+#[test]
+fn remove_asset_requires_ownership() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(2), 0, RemovalReason::Withdrawn),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn removed_asset_id_cannot_be_registered_again() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Destroyed));
+		// Ids only grow, the removed id keeps its tombstone.
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_eq!(WivSupplyChain::next_asset_id(), 2);
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![1]);
+		assert!(WivSupplyChain::tombstone(0).is_some());
+	});
+}
+
+#[test]
+fn provenance_log_records_every_action() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Timestamp::set_timestamp(6_000);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		System::set_block_number(2);
+		Timestamp::set_timestamp(12_000);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(2), 0, RemovalReason::Consumed));
+		// The log survives the removal of the asset.
+		assert_eq!(WivSupplyChain::history(0, 0, 10), vec![
+			ProvenanceEntry {
+				action: ProvenanceAction::Created,
+				actor: 1,
+				counterparty: None,
+				block: 1,
+				timestamp: 6_000,
+			},
+			ProvenanceEntry {
+				action: ProvenanceAction::Transferred,
+				actor: 1,
+				counterparty: Some(2),
+				block: 2,
+				timestamp: 12_000,
+			},
+			ProvenanceEntry {
+				action: ProvenanceAction::Removed,
+				actor: 2,
+				counterparty: None,
+				block: 2,
+				timestamp: 12_000,
+			},
+		]);
+	});
+}
+
+#[test]
+fn provenance_log_survives_many_transfers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		for n in 1..=200u64 {
+			System::set_block_number(n);
+			assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(n), 0, n + 1));
+		}
+		assert_eq!(WivSupplyChain::history_length(0), 201);
+		// Entries are returned page by page, oldest first.
+		let page = WivSupplyChain::history(0, 2, 50);
+		assert_eq!(page.len(), 50);
+		assert_eq!(page[0].actor, 100);
+		assert_eq!(page[0].counterparty, Some(101));
+		assert_eq!(page[0].block, 100);
+		let last_page = WivSupplyChain::history(0, 4, 50);
+		assert_eq!(last_page.len(), 1);
+		assert_eq!(last_page[0].counterparty, Some(201));
+		assert!(WivSupplyChain::history(0, 5, 50).is_empty());
+		// Every entry is kept.
+		let all: Vec<_> = (0..5).flat_map(|page| WivSupplyChain::history(0, page, 50)).collect();
+		assert_eq!(all.len(), 201);
+		assert!(all[1..].iter().all(|entry| entry.action == ProvenanceAction::Transferred));
+	});
+}
+
+#[test]
+fn storage_deposit_is_reserved_by_each_owner_and_released() {
+	new_test_ext().execute_with(|| {
+		let deposit = asset_deposit();
+		assert_eq!(WivSupplyChain::deposit_for(ASSET.len(), &metadata()), deposit);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(Balances::free_balance(1), 10_000 - deposit);
+		// The new owner reserves its own deposit, the previous owner gets its deposit back.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 10_000);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert_eq!(Balances::free_balance(2), 1_000 - deposit);
+		// An owner unable to reserve the deposit can't receive the asset.
+		assert_noop!(
+			WivSupplyChain::transfer_asset(Origin::signed(2), 0, 301),
+			Error::<Test>::InsufficientDeposit
+		);
+		// The deposit is given back to the owner on removal.
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(2), 0, RemovalReason::Consumed));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000);
+	});
+}
+
+#[test]
+fn new_asset_requires_the_storage_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 999, Role::Producer));
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(999), ASSET.to_vec()),
+			Error::<Test>::InsufficientDeposit
+		);
+	});
+}
+
+#[test]
+fn roles_are_managed_by_the_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert!(WivSupplyChain::has_role(&1, Role::Producer));
+		assert_noop!(
+			WivSupplyChain::grant_role(Origin::signed(1), 2, Role::Inspector),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 2, Role::Inspector));
+		assert!(WivSupplyChain::has_role(&2, Role::Inspector));
+		assert!(!WivSupplyChain::has_role(&2, Role::Insurer));
+		assert_noop!(
+			WivSupplyChain::grant_role(Origin::root(), 2, Role::Inspector),
+			Error::<Test>::RoleAlreadyGranted
+		);
+		assert_noop!(
+			WivSupplyChain::revoke_role(Origin::signed(2), 2, Role::Inspector),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(WivSupplyChain::revoke_role(Origin::root(), 2, Role::Inspector));
+		assert!(!WivSupplyChain::has_role(&2, Role::Inspector));
+		assert_noop!(
+			WivSupplyChain::revoke_role(Origin::root(), 2, Role::Inspector),
+			Error::<Test>::RoleNotGranted
+		);
+	});
+}
+
+#[test]
+fn only_producers_register_assets() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(2), ASSET.to_vec()),
+			Error::<Test>::MissingRole
+		);
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 2, Role::Producer));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(2), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::revoke_role(Origin::root(), 2, Role::Producer));
+		assert_noop!(
+			WivSupplyChain::new_asset(Origin::signed(2), ASSET.to_vec()),
+			Error::<Test>::MissingRole
+		);
+	});
+}
+
+#[test]
+fn accepted_offer_hands_over_the_custody() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_eq!(WivSupplyChain::offer(0), Some(TransferOffer { from: 1, to: 2, expires: 11, attestation: None }));
+		// Nothing moves until the receiver accepts.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.custodian), Some(1));
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(3), 0), Error::<Test>::NotReceiver);
+		assert_ok!(WivSupplyChain::accept_transfer(Origin::signed(2), 0));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.custodian), Some(2));
+		assert_eq!(WivSupplyChain::assets_in_custody(&2), vec![0]);
+		assert!(WivSupplyChain::assets_in_custody(&1).is_empty());
+		// The owner is unchanged.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(1));
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![0]);
+		assert_eq!(WivSupplyChain::offer(0), None);
+		let entry = &WivSupplyChain::history(0, 1, 1)[0];
+		assert_eq!(entry.action, ProvenanceAction::Stored);
+		assert_eq!(entry.counterparty, Some(2));
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(2), 0), Error::<Test>::NoOffer);
+	});
+}
+
+#[test]
+fn rejected_or_cancelled_offer_keeps_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_noop!(WivSupplyChain::reject_transfer(Origin::signed(1), 0), Error::<Test>::NotReceiver);
+		assert_ok!(WivSupplyChain::reject_transfer(Origin::signed(2), 0));
+		assert_eq!(WivSupplyChain::offer(0), None);
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 3, None));
+		assert_noop!(WivSupplyChain::cancel_offer(Origin::signed(3), 0), Error::<Test>::NotCustodian);
+		assert_ok!(WivSupplyChain::cancel_offer(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::offer(0), None);
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.custodian), Some(1));
+		assert_noop!(WivSupplyChain::cancel_offer(Origin::signed(1), 0), Error::<Test>::NoOffer);
+	});
+}
+
+#[test]
+fn pending_offer_locks_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(WivSupplyChain::offer_transfer(Origin::signed(2), 0, 3, None), Error::<Test>::NotCustodian);
+		assert_noop!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 4, None), Error::<Test>::MissingRole);
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_noop!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 3, None), Error::<Test>::PendingOffer);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 3), Error::<Test>::PendingOffer);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Withdrawn),
+			Error::<Test>::PendingOffer
+		);
+	});
+}
+
+#[test]
+fn offers_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		WivSupplyChain::on_initialize(10);
+		assert!(WivSupplyChain::offer(0).is_some());
+		WivSupplyChain::on_initialize(11);
+		assert_eq!(WivSupplyChain::offer(0), None);
+		System::set_block_number(11);
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(2), 0), Error::<Test>::NoOffer);
+		// A new offer isn't affected by the expiry of a previous one.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		WivSupplyChain::on_initialize(11);
+		assert_eq!(WivSupplyChain::offer(0).map(|o| o.expires), Some(21));
+	});
+}
+
+#[test]
+fn ownership_and_custody_move_independently() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		// The producer stores the asset in a warehouse.
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		// An investor buys it, the bottle stays in the warehouse.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 10));
+		let details = WivSupplyChain::asset(0).unwrap();
+		assert_eq!((details.owner, details.custodian), (10, 2));
+		assert_eq!(WivSupplyChain::assets_of(&10), vec![0]);
+		assert_eq!(WivSupplyChain::assets_in_custody(&2), vec![0]);
+		// Neither party can make the other's move.
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(2), 0, 3), Error::<Test>::NotOwner);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(10), 0, 3), Error::<Test>::NotCustodian);
+		// The warehouse moves the asset to another warehouse, the owner is unchanged.
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(2), 0, 3));
+		let details = WivSupplyChain::asset(0).unwrap();
+		assert_eq!((details.owner, details.custodian), (10, 3));
+		assert!(WivSupplyChain::assets_in_custody(&2).is_empty());
+		// Custody can only be handed to custodians.
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(3), 0, 10), Error::<Test>::MissingRole);
+		// Removal clears both indices.
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(10), 0, RemovalReason::Consumed));
+		assert!(WivSupplyChain::assets_of(&10).is_empty());
+		assert!(WivSupplyChain::assets_in_custody(&3).is_empty());
+	});
+}
+
+/// Register a pallet (0) holding two cases (1, 2) of bottles (3, 4 and 5), and a loose bottle (6)
+fn pack_pallet() {
+	for _ in 0..7 {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+	}
+	assert_ok!(WivSupplyChain::pack(Origin::signed(1), 1, vec![3, 4]));
+	assert_ok!(WivSupplyChain::pack(Origin::signed(1), 2, vec![5]));
+	assert_ok!(WivSupplyChain::pack(Origin::signed(1), 0, vec![1, 2]));
+}
+
+fn leaf(id: u64) -> AssetTree {
+	AssetTree { id, children: vec![] }
+}
+
+#[test]
+fn pack_builds_the_containment_tree() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		assert_eq!(WivSupplyChain::tree(0), Some(AssetTree {
+			id: 0,
+			children: vec![
+				AssetTree { id: 1, children: vec![leaf(3), leaf(4)] },
+				AssetTree { id: 2, children: vec![leaf(5)] },
+			],
+		}));
+		assert_eq!(WivSupplyChain::tree(6), Some(leaf(6)));
+		assert_eq!(WivSupplyChain::tree(7), None);
+		assert_eq!(WivSupplyChain::parent_of(3), Some(1));
+		assert_eq!(WivSupplyChain::ancestors(3), vec![1, 0]);
+		assert_eq!(WivSupplyChain::descendants(0), vec![1, 2, 3, 4, 5]);
+		// More bottles can be added to a case already on the pallet.
+		assert_ok!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]));
+		assert_eq!(WivSupplyChain::children_of(2), vec![5, 6]);
+	});
+}
+
+#[test]
+fn transfers_move_nested_units() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		// The pallet is shipped to a warehouse with everything packed in it.
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		assert_eq!(WivSupplyChain::assets_in_custody(&2), vec![0, 1, 2, 3, 4, 5]);
+		assert_eq!(WivSupplyChain::assets_in_custody(&1), vec![6]);
+		assert_eq!(WivSupplyChain::history_length(4), 2);
+		// Then handed over to another warehouse.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(2), 0, 3, None));
+		assert_ok!(WivSupplyChain::accept_transfer(Origin::signed(3), 0));
+		assert_eq!(WivSupplyChain::assets_in_custody(&3), vec![0, 1, 2, 3, 4, 5]);
+		// Selling the pallet sells its cases and bottles, the buyer reserves their deposits.
+		assert_ok!(Balances::transfer(Origin::signed(1), 10, 1_000));
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 10));
+		assert_eq!(WivSupplyChain::assets_of(&10), vec![0, 1, 2, 3, 4, 5]);
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![6]);
+		let deposit = asset_deposit();
+		assert_eq!(Balances::reserved_balance(10), 6 * deposit);
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		// The nesting is unchanged.
+		assert_eq!(WivSupplyChain::descendants(0), vec![1, 2, 3, 4, 5]);
+	});
+}
+
+#[test]
+fn packed_assets_move_with_their_parent() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(1), 3, 10), Error::<Test>::AssetPacked);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(1), 1, 2), Error::<Test>::AssetPacked);
+		assert_noop!(WivSupplyChain::offer_transfer(Origin::signed(1), 1, 2, None), Error::<Test>::AssetPacked);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 3, RemovalReason::Consumed),
+			Error::<Test>::AssetPacked
+		);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed),
+			Error::<Test>::NotEmpty
+		);
+		// Once unpacked, the case moves on its own and takes its bottles along.
+		assert_ok!(WivSupplyChain::unpack(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::parent_of(1), None);
+		assert_eq!(WivSupplyChain::tree(0), Some(leaf(0)));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 1, 2));
+		assert_eq!(WivSupplyChain::assets_in_custody(&2), vec![1, 3, 4]);
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 0), Error::<Test>::NothingToUnpack);
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed));
+	});
+}
+
+#[test]
+fn nested_assets_are_locked_with_their_ancestors() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		// The content of a pallet being handed over doesn't change.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::PendingOffer);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]), Error::<Test>::PendingOffer);
+		assert_ok!(WivSupplyChain::cancel_offer(Origin::signed(1), 0));
+		// Nor does the content of a lost pallet.
+		assert_ok!(WivSupplyChain::report_lost(Origin::signed(1), 0));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::InvalidStateTransition);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]), Error::<Test>::InvalidStateTransition);
+		assert_ok!(WivSupplyChain::report_found(Origin::signed(1), 0));
+		// The buyer of a listed pallet gets all of it.
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::ForSale);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]), Error::<Test>::ForSale);
+		assert_ok!(WivSupplyChain::delist(Origin::signed(1), 0));
+		// So does the winner of an auctioned pallet.
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 10));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::InAuction);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]), Error::<Test>::InAuction);
+		assert_ok!(WivSupplyChain::cancel_auction(Origin::signed(1), 0));
+		// And the holders of the shares of a pallet own all of it.
+		assert_ok!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 1));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::Fractionalised);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6]), Error::<Test>::Fractionalised);
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(4), 0, 1, 1));
+		assert_ok!(WivSupplyChain::redeem(Origin::signed(1), 0));
+		// A case is unpacked in a pallet at rest, its bottles stay on the pallet.
+		assert_ok!(WivSupplyChain::unpack(Origin::signed(1), 1));
+		assert_eq!(WivSupplyChain::children_of(1), vec![]);
+		assert_eq!(WivSupplyChain::ancestors(1), vec![0]);
+		assert_ok!(WivSupplyChain::pack(Origin::signed(1), 2, vec![3]));
+		assert_eq!(WivSupplyChain::ancestors(3), vec![2, 0]);
+	});
+}
+
+#[test]
+fn pack_checks_the_assets() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		// Both the parent and the children must be owned and held by the caller.
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 7, 2));
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![7]), Error::<Test>::NotCustodian);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(2), 7, vec![6]), Error::<Test>::NotOwner);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![9]), Error::<Test>::UnknownAsset);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![]), Error::<Test>::NothingToPack);
+		// An asset is packed in a single parent, once.
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![3]), Error::<Test>::AssetPacked);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![6, 6]), Error::<Test>::DuplicateAsset);
+		// No cycles.
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![6]), Error::<Test>::CyclicPacking);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 3, vec![0]), Error::<Test>::CyclicPacking);
+		// A pallet can't go in a bottle, nor a bottle in a bottle on a pallet.
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![0]), Error::<Test>::TooDeep);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 3, vec![8]), Error::<Test>::TooDeep);
+		// A case holds at most three bottles.
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 1, vec![6, 8]), Error::<Test>::TooManyChildren);
+		// Pending custody offers lock the assets.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 6, 2, None));
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 6, vec![8]), Error::<Test>::PendingOffer);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 8, vec![6]), Error::<Test>::PendingOffer);
+	});
+}
+
+/// Asset data of a lot of the given quantity
+fn lot(name: &str, quantity: u64) -> Vec<u8> {
+	encode(&format!(
+		r#"{{"producer":"Wiv","format":"barrel","lot":"{}","location":"Bordeaux","quantity":{}}}"#,
+		name, quantity
+	))
+}
+
+fn actions(asset_id: u64) -> Vec<ProvenanceAction> {
+	let length = WivSupplyChain::history_length(asset_id);
+	WivSupplyChain::history(asset_id, 0, length).into_iter().map(|entry| entry.action).collect()
+}
+
+#[test]
+fn split_asset_conserves_the_quantity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot("B1", 300)));
+		assert_noop!(
+			WivSupplyChain::split_asset(Origin::signed(1), 0, vec![AssetInput { data: lot("B1-a", 200) }]),
+			Error::<Test>::QuantityMismatch
+		);
+		let outputs = vec![AssetInput { data: lot("B1-a", 100) }; 3];
+		assert_ok!(WivSupplyChain::split_asset(Origin::signed(1), 0, outputs));
+		// The barrel is replaced by the new assets.
+		assert_eq!(WivSupplyChain::asset(0), None);
+		assert_eq!(WivSupplyChain::tombstone(0).map(|t| t.reason), Some(RemovalReason::Split));
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![1, 2, 3]);
+		assert_eq!(WivSupplyChain::next_asset_id(), 4);
+		for asset_id in 1..4 {
+			assert_eq!(WivSupplyChain::asset(asset_id).map(|a| a.metadata.quantity), Some(100));
+			assert_eq!(actions(asset_id), vec![ProvenanceAction::Created, ProvenanceAction::SplitFrom(0)]);
+			assert_eq!(WivSupplyChain::sources_of(asset_id), vec![0]);
+		}
+		// The deposit of the barrel is replaced by the deposits of the new assets.
+		let metadata = AssetMetadata {
+			producer: b"Wiv".to_vec(),
+			vintage: None,
+			format: b"barrel".to_vec(),
+			lot: b"B1-a".to_vec(),
+			location: b"Bordeaux".to_vec(),
+			quantity: 100,
+		};
+		let deposit = 10 + (lot("B1-a", 100).len() + metadata.encoded_size()) as u64;
+		assert_eq!(Balances::reserved_balance(1), 3 * deposit);
+	});
+}
+
+#[test]
+fn split_asset_checks_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot("B1", 2)));
+		let outputs = vec![AssetInput { data: lot("B1-a", 1) }; 2];
+		assert_noop!(
+			WivSupplyChain::split_asset(Origin::signed(2), 0, outputs.clone()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(WivSupplyChain::split_asset(Origin::signed(1), 0, vec![]), Error::<Test>::EmptyBatch);
+		assert_noop!(
+			WivSupplyChain::split_asset(Origin::signed(1), 0, vec![AssetInput { data: lot("B1-a", 1) }; 5]),
+			Error::<Test>::BatchTooLarge
+		);
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_noop!(
+			WivSupplyChain::split_asset(Origin::signed(1), 0, outputs.clone()),
+			Error::<Test>::PendingOffer
+		);
+		assert_ok!(WivSupplyChain::accept_transfer(Origin::signed(2), 0));
+		assert_noop!(
+			WivSupplyChain::split_asset(Origin::signed(1), 0, outputs),
+			Error::<Test>::NotCustodian
+		);
+		// Quantities are positive, splits are only recorded by split_asset.
+		assert_noop!(WivSupplyChain::new_asset(Origin::signed(1), lot("B2", 0)), Error::<Test>::InvalidQuantity);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Split),
+			Error::<Test>::InvalidReason
+		);
+	});
+}
+
+#[test]
+fn merged_assets_trace_back_to_every_source_lot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot("A", 300)));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot("B", 200)));
+		// Half of lot A is blended with lot B.
+		let halves = vec![AssetInput { data: lot("A", 150) }; 2];
+		assert_ok!(WivSupplyChain::split_asset(Origin::signed(1), 0, halves));
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(1), vec![3, 1], lot("AB", 300)),
+			Error::<Test>::QuantityMismatch
+		);
+		assert_ok!(WivSupplyChain::merge_assets(Origin::signed(1), vec![3, 1], lot("AB", 350)));
+		assert_eq!(WivSupplyChain::asset(4).map(|a| a.metadata.quantity), Some(350));
+		assert_eq!(WivSupplyChain::tombstone(1).map(|t| t.reason), Some(RemovalReason::Merged));
+		assert_eq!(WivSupplyChain::tombstone(3).map(|t| t.reason), Some(RemovalReason::Merged));
+		assert_eq!(actions(4), vec![
+			ProvenanceAction::Created,
+			ProvenanceAction::MergedFrom(3),
+			ProvenanceAction::MergedFrom(1),
+		]);
+		// The blend is bottled, a bottle traces back to both lots.
+		let bottles = vec![AssetInput { data: lot("AB", 175) }; 2];
+		assert_ok!(WivSupplyChain::split_asset(Origin::signed(1), 4, bottles));
+		assert_eq!(WivSupplyChain::lineage(5), vec![4, 3, 1, 0]);
+		assert_eq!(WivSupplyChain::lineage(2), vec![0]);
+		assert!(WivSupplyChain::lineage(0).is_empty());
+		assert_eq!(WivSupplyChain::assets_of(&1), vec![2, 5, 6]);
+	});
+}
+
+#[test]
+fn merge_assets_checks_the_sources() {
+	new_test_ext().execute_with(|| {
+		for name in &["A", "B", "C"] {
+			assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot(name, 10)));
+		}
+		assert_noop!(WivSupplyChain::merge_assets(Origin::signed(1), vec![], lot("AB", 10)), Error::<Test>::EmptyBatch);
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(1), vec![0, 0], lot("AA", 20)),
+			Error::<Test>::DuplicateAsset
+		);
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(2), vec![0, 1], lot("AB", 20)),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(WivSupplyChain::pack(Origin::signed(1), 2, vec![1]));
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(1), vec![0, 1], lot("AB", 20)),
+			Error::<Test>::AssetPacked
+		);
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(1), vec![0, 2], lot("AC", 20)),
+			Error::<Test>::NotEmpty
+		);
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed));
+		assert_noop!(
+			WivSupplyChain::merge_assets(Origin::signed(1), vec![0, 2], lot("AC", 20)),
+			Error::<Test>::AssetRemoved
+		);
+	});
+}
+
+#[test]
+fn anchored_asset_keeps_only_the_hash_on_chain() {
+	new_test_ext().execute_with(|| {
+		let document = base64::decode(ASSET).unwrap();
+		let digest = H256::from(blake2_256(&document));
+		let uri = b"ipfs://QmWiv".to_vec();
+		assert_ok!(WivSupplyChain::new_anchored_asset(Origin::signed(1), digest, uri.clone()));
+		let details = WivSupplyChain::asset(0).unwrap();
+		assert!(details.data.is_empty());
+		assert_eq!(details.anchor, Some(DocumentAnchor { digest, uri: uri.clone() }));
+		assert_eq!(details.metadata.quantity, 1);
+		// The deposit only covers the digest and the uri.
+		let metadata = AssetMetadata { quantity: 1, ..Default::default() };
+		assert_eq!(details.deposit, 10 + (32 + uri.len() + metadata.encoded_size()) as u64);
+		assert_eq!(Balances::reserved_balance(1), details.deposit);
+		// Anchored assets move like any other asset.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 10));
+		assert_eq!(WivSupplyChain::assets_of(&10), vec![0]);
+	});
+}
+
+#[test]
+fn documents_are_verified_against_the_chain() {
+	new_test_ext().execute_with(|| {
+		let document = base64::decode(ASSET).unwrap();
+		let digest = H256::from(blake2_256(&document));
+		assert_ok!(WivSupplyChain::new_anchored_asset(Origin::signed(1), digest, b"ipfs://QmWiv".to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert!(WivSupplyChain::verify_document(0, &document));
+		assert!(!WivSupplyChain::verify_document(0, b"{\"producer\":\"Someone else\"}"));
+		// Documents stored on chain are compared as they are.
+		assert!(WivSupplyChain::verify_document(1, ASSET));
+		assert!(!WivSupplyChain::verify_document(1, &document));
+		assert!(!WivSupplyChain::verify_document(2, &document));
+	});
+}
+
+#[test]
+fn new_anchored_asset_checks_the_uri() {
+	new_test_ext().execute_with(|| {
+		let digest = H256::repeat_byte(7);
+		assert_noop!(
+			WivSupplyChain::new_anchored_asset(Origin::signed(1), digest, vec![]),
+			Error::<Test>::TooShort
+		);
+		assert_noop!(
+			WivSupplyChain::new_anchored_asset(Origin::signed(1), digest, vec![b'x'; 65]),
+			Error::<Test>::TooLong
+		);
+		assert_noop!(
+			WivSupplyChain::new_anchored_asset(Origin::signed(2), digest, b"ipfs://QmWiv".to_vec()),
+			Error::<Test>::MissingRole
+		);
+	});
+}
+
+#[test]
+fn confidential_metadata_is_disclosed_to_recipients() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::register_encryption_key(Origin::signed(2), [2; 32]));
+		assert_ok!(WivSupplyChain::register_encryption_key(Origin::signed(3), [3; 32]));
+		assert_eq!(WivSupplyChain::encryption_key(&2), Some([2; 32]));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		let deposit = asset_deposit();
+		assert_ok!(WivSupplyChain::set_confidential_metadata(
+			Origin::signed(1), 0, vec![7; 40], vec![(2, vec![2; 80])]
+		));
+		// The encrypted bytes are part of the asset deposit.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.deposit), Some(deposit + 120));
+		assert_eq!(Balances::reserved_balance(1), deposit + 120);
+		assert_ok!(WivSupplyChain::grant_access(Origin::signed(1), 0, 3, vec![3; 80]));
+		assert_ok!(WivSupplyChain::revoke_access(Origin::signed(1), 0, 2));
+		assert_eq!(WivSupplyChain::confidential(0).map(|c| c.wrapped_keys), Some(vec![(3, vec![3; 80])]));
+		assert_eq!(Balances::reserved_balance(1), deposit + 120);
+		assert_noop!(WivSupplyChain::revoke_access(Origin::signed(1), 0, 2), Error::<Test>::NoAccess);
+		// Granting again replaces the wrapped key.
+		assert_ok!(WivSupplyChain::grant_access(Origin::signed(1), 0, 3, vec![3; 60]));
+		assert_eq!(WivSupplyChain::confidential(0).map(|c| c.wrapped_keys), Some(vec![(3, vec![3; 60])]));
+		assert_eq!(Balances::reserved_balance(1), deposit + 100);
+		// New metadata replaces the previous one and its recipients.
+		assert_ok!(WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![8; 20], vec![]));
+		assert_eq!(WivSupplyChain::confidential(0).map(|c| c.wrapped_keys.len()), Some(0));
+		assert_eq!(Balances::reserved_balance(1), deposit + 20);
+	});
+}
+
+#[test]
+fn confidential_metadata_follows_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::register_encryption_key(Origin::signed(2), [2; 32]));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::set_confidential_metadata(
+			Origin::signed(1), 0, vec![7; 40], vec![(2, vec![2; 80])]
+		));
+		let deposit = asset_deposit() + 120;
+		// Only the owner discloses the metadata, the new owner reserves the whole deposit.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 10));
+		assert_noop!(WivSupplyChain::revoke_access(Origin::signed(1), 0, 2), Error::<Test>::NotOwner);
+		assert_eq!(Balances::reserved_balance(10), deposit);
+		assert_ok!(WivSupplyChain::revoke_access(Origin::signed(10), 0, 2));
+		assert_eq!(Balances::reserved_balance(10), deposit - 80);
+		// Removal drops the metadata and gives the whole deposit back.
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(10), 0, RemovalReason::Consumed));
+		assert_eq!(WivSupplyChain::confidential(0), None);
+		assert_eq!(Balances::reserved_balance(10), 0);
+	});
+}
+
+#[test]
+fn confidential_metadata_checks_the_recipients() {
+	new_test_ext().execute_with(|| {
+		for who in 2..6 {
+			assert_ok!(WivSupplyChain::register_encryption_key(Origin::signed(who), [who as u8; 32]));
+		}
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::set_confidential_metadata(Origin::signed(2), 0, vec![7; 40], vec![]),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![], vec![]),
+			Error::<Test>::TooShort
+		);
+		assert_noop!(
+			WivSupplyChain::grant_access(Origin::signed(1), 0, 2, vec![2; 80]),
+			Error::<Test>::NoConfidentialMetadata
+		);
+		let keys = |recipients: &[u64]| recipients.iter().map(|&who| (who, vec![who as u8; 80])).collect::<Vec<_>>();
+		assert_noop!(
+			WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![7; 40], keys(&[2, 3, 4, 5])),
+			Error::<Test>::TooManyRecipients
+		);
+		assert_noop!(
+			WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![7; 40], keys(&[2, 3, 2])),
+			Error::<Test>::DuplicateRecipient
+		);
+		assert_noop!(
+			WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![7; 40], keys(&[2, 6])),
+			Error::<Test>::NoEncryptionKey
+		);
+		assert_ok!(WivSupplyChain::set_confidential_metadata(Origin::signed(1), 0, vec![7; 40], keys(&[2, 3, 4])));
+		assert_noop!(
+			WivSupplyChain::grant_access(Origin::signed(1), 0, 5, vec![5; 80]),
+			Error::<Test>::TooManyRecipients
+		);
+		assert_noop!(WivSupplyChain::grant_access(Origin::signed(1), 0, 2, vec![]), Error::<Test>::InvalidWrappedKey);
+		assert_noop!(
+			WivSupplyChain::grant_access(Origin::signed(1), 0, 2, vec![2; 129]),
+			Error::<Test>::InvalidWrappedKey
+		);
+	});
+}
+
+#[test]
+fn attestations_are_kept_in_time_order() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::Ullage, H256::repeat_byte(1), 90));
+		assert_ok!(WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::Label, H256::repeat_byte(2), 70));
+		System::set_block_number(2);
+		assert_ok!(WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::Ullage, H256::repeat_byte(3), 85));
+		let scores = |attestations: Vec<Attestation<u64, u64, u64>>| {
+			attestations.into_iter().map(|a| (a.kind, a.score)).collect::<Vec<_>>()
+		};
+		assert_eq!(WivSupplyChain::attestation_count(0), 3);
+		assert_eq!(
+			scores(WivSupplyChain::attestations(0, 0, 10)),
+			vec![(AttestationKind::Ullage, 90), (AttestationKind::Label, 70), (AttestationKind::Ullage, 85)]
+		);
+		// Only the latest attestation of each kind is returned.
+		assert_eq!(
+			scores(WivSupplyChain::latest_attestations(0)),
+			vec![(AttestationKind::Ullage, 85), (AttestationKind::Label, 70)]
+		);
+		assert_eq!(
+			WivSupplyChain::latest_attestation(0, AttestationKind::Ullage),
+			Some(Attestation {
+				kind: AttestationKind::Ullage,
+				inspector: 5,
+				data_hash: H256::repeat_byte(3),
+				score: 85,
+				block: 2,
+				timestamp: 0,
+			})
+		);
+		assert_eq!(WivSupplyChain::latest_attestation(0, AttestationKind::Capsule), None);
+		// Each attestation is part of the provenance of the asset.
+		assert_eq!(actions(0).iter().filter(|&&a| a == ProvenanceAction::Inspected).count(), 3);
+	});
+}
+
+#[test]
+fn attest_checks_the_inspector_and_the_score() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::attest(Origin::signed(1), 0, AttestationKind::Capsule, H256::zero(), 50),
+			Error::<Test>::MissingRole
+		);
+		assert_noop!(
+			WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::Capsule, H256::zero(), 101),
+			Error::<Test>::InvalidScore
+		);
+		assert_noop!(
+			WivSupplyChain::attest(Origin::signed(5), 1, AttestationKind::Capsule, H256::zero(), 50),
+			Error::<Test>::UnknownAsset
+		);
+	});
+}
+
+#[test]
+fn offer_can_require_a_recent_attestation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, Some(AttestationKind::StorageTemperature)));
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(2), 0), Error::<Test>::MissingAttestation);
+		// An attestation of another kind doesn't count.
+		assert_ok!(WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::Label, H256::zero(), 80));
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(2), 0), Error::<Test>::MissingAttestation);
+		assert_ok!(WivSupplyChain::attest(Origin::signed(5), 0, AttestationKind::StorageTemperature, H256::zero(), 80));
+		assert_ok!(WivSupplyChain::accept_transfer(Origin::signed(2), 0));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.custodian), Some(2));
+		// The attestation is no longer recent once the validity has elapsed.
+		System::set_block_number(22);
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(2), 0, 3, Some(AttestationKind::StorageTemperature)));
+		assert_noop!(WivSupplyChain::accept_transfer(Origin::signed(3), 0), Error::<Test>::MissingAttestation);
+	});
+}
+
+/// Telemetry endpoint of the node running the tests
+const ENDPOINT: &str = "http://localhost:8080/telemetry";
+
+fn london_bond(min_temperature: i16, max_temperature: i16) -> EnvironmentReading {
+	EnvironmentReading {
+		location: b"London Bond".to_vec(),
+		min_temperature,
+		max_temperature,
+		min_humidity: 690,
+		max_humidity: 705,
+		samples: 2,
+	}
+}
+
+/// Readings collected at `block_number`, signed by `reporter`
+fn telemetry(
+	reporter: u64,
+	block_number: u64,
+	readings: Vec<EnvironmentReading>,
+) -> (TelemetryPayload<UintAuthorityId, u64>, TestSignature) {
+	let payload = TelemetryPayload { block_number, readings, public: UintAuthorityId(reporter) };
+	let signature = TestSignature(reporter, payload.encode());
+	(payload, signature)
+}
+
+#[test]
+fn offchain_worker_submits_aggregated_readings() {
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: ENDPOINT.into(),
+		response: Some(br#"[
+			{"location":"London Bond","temperature":128,"humidity":705},
+			{"location":"Bordeaux","temperature":141,"humidity":652},
+			{"location":"London Bond","temperature":135,"humidity":690}
+		]"#.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+	// The custodian 2 has its key in the keystore of the node.
+	UintAuthorityId::set_all_keys(vec![2u64]);
+	t.execute_with(|| {
+		// Nodes without an endpoint don't poll.
+		WivSupplyChain::offchain_worker(5);
+		assert!(pool_state.read().transactions.is_empty());
+		sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, TELEMETRY_ENDPOINT_KEY, ENDPOINT.as_bytes());
+		// The endpoint is only polled every interval.
+		WivSupplyChain::offchain_worker(4);
+		assert!(pool_state.read().transactions.is_empty());
+		WivSupplyChain::offchain_worker(5);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		match tx.call {
+			crate::Call::submit_environment_unsigned(payload, signature) => {
+				assert_eq!(payload.block_number, 5);
+				assert_eq!(payload.public, UintAuthorityId(2));
+				// Samples are aggregated per location.
+				assert_eq!(payload.readings, vec![
+					EnvironmentReading {
+						location: b"Bordeaux".to_vec(),
+						min_temperature: 141,
+						max_temperature: 141,
+						min_humidity: 652,
+						max_humidity: 652,
+						samples: 1,
+					},
+					london_bond(128, 135),
+				]);
+				assert!(SignedPayload::<Test>::verify::<TestAuthId>(&payload, signature));
+			}
+			_ => panic!("unexpected call"),
+		}
+	});
+}
+
+#[test]
+fn readings_outside_the_tolerance_are_breaches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 1, 2));
+		let tolerance = EnvironmentTolerance {
+			min_temperature: 100,
+			max_temperature: 140,
+			min_humidity: 600,
+			max_humidity: 750,
+		};
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, Some(tolerance)));
+		assert_ok!(WivSupplyChain::set_tolerance(
+			Origin::signed(1), 1, Some(EnvironmentTolerance { max_temperature: 130, ..tolerance })
+		));
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_ok!(WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature));
+		// Only the asset whose tolerance is exceeded records a breach.
+		assert!(!actions(0).contains(&ProvenanceAction::EnvironmentBreach));
+		assert_eq!(actions(1).last(), Some(&ProvenanceAction::EnvironmentBreach));
+		assert_eq!(WivSupplyChain::history(1, 1, 1)[0].actor, 2);
+		assert_eq!(WivSupplyChain::latest_reading(b"London Bond".to_vec()), Some((5, london_bond(128, 135))));
+		assert_eq!(WivSupplyChain::last_telemetry(&2), Some(5));
+	});
+}
+
+#[test]
+fn only_the_custodian_reports_the_environment_of_an_asset() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		let tolerance = EnvironmentTolerance {
+			min_temperature: 100,
+			max_temperature: 130,
+			min_humidity: 600,
+			max_humidity: 750,
+		};
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, Some(tolerance)));
+		// Another custodian using the same location name doesn't breach the asset.
+		let (payload, signature) = telemetry(3, 5, vec![london_bond(128, 135)]);
+		assert_ok!(WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature));
+		assert!(!actions(0).contains(&ProvenanceAction::EnvironmentBreach));
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_ok!(WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature));
+		assert_eq!(actions(0).last(), Some(&ProvenanceAction::EnvironmentBreach));
+		// The tolerance is cleared when the custody moves.
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(2), 0, 3));
+		assert_eq!(WivSupplyChain::tolerance(0), None);
+		assert_eq!(WivSupplyChain::monitored_count(b"London Bond".to_vec()), 0);
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, Some(tolerance)));
+		assert_eq!(WivSupplyChain::monitored_count(b"London Bond".to_vec()), 1);
+	});
+}
+
+#[test]
+fn telemetry_is_validated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		let validate = |(payload, signature): (TelemetryPayload<UintAuthorityId, u64>, TestSignature)| {
+			WivSupplyChain::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::submit_environment_unsigned(payload, signature),
+			)
+		};
+		assert!(validate(telemetry(2, 5, vec![london_bond(128, 135)])).is_ok());
+		// The payload must be signed by a custodian.
+		let (payload, _) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		let forged = TestSignature(3, payload.encode());
+		assert_eq!(validate((payload, forged)), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(telemetry(4, 5, vec![london_bond(128, 135)])), Err(InvalidTransaction::BadProof.into()));
+		assert_eq!(validate(telemetry(2, 6, vec![london_bond(128, 135)])), Err(InvalidTransaction::Future.into()));
+		assert_eq!(validate(telemetry(2, 5, vec![london_bond(135, 128)])), Err(InvalidTransaction::Call.into()));
+		assert_eq!(validate(telemetry(2, 5, vec![london_bond(128, 135); 4])), Err(InvalidTransaction::Call.into()));
+		assert_eq!(validate(telemetry(2, 5, vec![])), Err(InvalidTransaction::Call.into()));
+		// Readings can't be replayed.
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_ok!(WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature));
+		assert_eq!(validate(telemetry(2, 5, vec![london_bond(128, 135)])), Err(InvalidTransaction::Stale.into()));
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_noop!(
+			WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature),
+			Error::<Test>::StaleTelemetry
+		);
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_noop!(
+			WivSupplyChain::submit_environment_unsigned(Origin::signed(2), payload, signature),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_tolerance_checks_the_asset() {
+	new_test_ext().execute_with(|| {
+		let tolerance = EnvironmentTolerance {
+			min_temperature: 100,
+			max_temperature: 140,
+			min_humidity: 600,
+			max_humidity: 750,
+		};
+		for _ in 0..3 {
+			assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		}
+		assert_ok!(WivSupplyChain::new_anchored_asset(Origin::signed(1), H256::zero(), b"ipfs://doc".to_vec()));
+		assert_noop!(WivSupplyChain::set_tolerance(Origin::signed(2), 0, Some(tolerance)), Error::<Test>::NotOwner);
+		assert_noop!(
+			WivSupplyChain::set_tolerance(Origin::signed(1), 0, Some(EnvironmentTolerance { min_humidity: 800, ..tolerance })),
+			Error::<Test>::InvalidTolerance
+		);
+		assert_noop!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, None), Error::<Test>::NotMonitored);
+		assert_noop!(WivSupplyChain::set_tolerance(Origin::signed(1), 3, Some(tolerance)), Error::<Test>::UnknownLocation);
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, Some(tolerance)));
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 1, Some(tolerance)));
+		// Updating a tolerance doesn't take a new slot.
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 1, Some(tolerance)));
+		assert_eq!(WivSupplyChain::monitored_count(b"London Bond".to_vec()), 2);
+		assert_noop!(
+			WivSupplyChain::set_tolerance(Origin::signed(1), 2, Some(tolerance)),
+			Error::<Test>::TooManyMonitoredAssets
+		);
+		// Clearing a tolerance or removing the asset frees its slot.
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 0, None));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 1, RemovalReason::Consumed));
+		assert_eq!(WivSupplyChain::tolerance(1), None);
+		assert_eq!(WivSupplyChain::monitored_count(b"London Bond".to_vec()), 0);
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(1), 2, Some(tolerance)));
+	});
+}
+
+/// Premiums of 50 every 10 blocks
+fn premium() -> PremiumSchedule<u64, u64> {
+	PremiumSchedule { amount: 50, period: 10 }
+}
+
+#[test]
+fn policy_covers_the_assets_while_premiums_are_paid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0, 1], 500, premium(), 100));
+		let policy = WivSupplyChain::policy(0).unwrap();
+		assert_eq!((policy.insurer, policy.holder, policy.status), (6, 1, PolicyStatus::Active));
+		assert_eq!(WivSupplyChain::policies_of(1), vec![0]);
+		// The coverage is reserved from the insurer.
+		assert_eq!(Balances::reserved_balance(6), 500);
+		// The assets are not covered before the first premium.
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(1), 0, 100, H256::zero()), Error::<Test>::PremiumDue);
+		assert_ok!(WivSupplyChain::pay_premium(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(6), 550);
+		assert_eq!(WivSupplyChain::policy(0).unwrap().paid_until, 11);
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(2), 0, 100, H256::zero()), Error::<Test>::NotHolder);
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(1), 0, 0, H256::zero()), Error::<Test>::InvalidAmount);
+		assert_noop!(
+			WivSupplyChain::file_claim(Origin::signed(1), 0, 600, H256::zero()),
+			Error::<Test>::CoverageExceeded
+		);
+		let evidence = H256::from(blake2_256(b"broken bottle"));
+		assert_ok!(WivSupplyChain::file_claim(Origin::signed(1), 0, 300, evidence));
+		assert_eq!(WivSupplyChain::claim(0).unwrap().evidence, evidence);
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(1), 0, 100, H256::zero()), Error::<Test>::PendingClaim);
+		assert_noop!(WivSupplyChain::settle_claim(Origin::signed(1), 0, 300), Error::<Test>::NotInsurer);
+		assert_noop!(WivSupplyChain::settle_claim(Origin::signed(6), 0, 400), Error::<Test>::PayoutTooHigh);
+		let free = Balances::free_balance(1);
+		assert_ok!(WivSupplyChain::settle_claim(Origin::signed(6), 0, 200));
+		assert_eq!(Balances::free_balance(1), free + 200);
+		assert_eq!(Balances::reserved_balance(6), 300);
+		assert_eq!(WivSupplyChain::claim(0), None);
+		assert_noop!(WivSupplyChain::settle_claim(Origin::signed(6), 0, 0), Error::<Test>::NoClaim);
+		// Payouts reduce the remaining coverage.
+		assert_noop!(
+			WivSupplyChain::file_claim(Origin::signed(1), 0, 301, H256::zero()),
+			Error::<Test>::CoverageExceeded
+		);
+		// The coverage lapses once the premiums paid run out.
+		System::set_block_number(11);
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(1), 0, 100, H256::zero()), Error::<Test>::PremiumDue);
+		assert_ok!(WivSupplyChain::pay_premium(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::policy(0).unwrap().paid_until, 21);
+	});
+}
+
+#[test]
+fn coverage_is_suspended_on_transfer_and_breach() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0, 1], 500, premium(), 100));
+		assert_ok!(WivSupplyChain::pay_premium(Origin::signed(1), 0));
+		assert_noop!(WivSupplyChain::reinstate_policy(Origin::signed(6), 0), Error::<Test>::NotSuspended);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4));
+		assert_eq!(
+			WivSupplyChain::policy(0).unwrap().status,
+			PolicyStatus::Suspended(SuspensionReason::Transferred)
+		);
+		assert_noop!(
+			WivSupplyChain::file_claim(Origin::signed(1), 0, 100, H256::zero()),
+			Error::<Test>::CoverageSuspended
+		);
+		// The policy is reinstated for a single owner of its assets.
+		assert_noop!(WivSupplyChain::reinstate_policy(Origin::signed(6), 0), Error::<Test>::MultipleOwners);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 1, 4));
+		assert_noop!(WivSupplyChain::reinstate_policy(Origin::signed(1), 0), Error::<Test>::NotInsurer);
+		assert_ok!(WivSupplyChain::reinstate_policy(Origin::signed(6), 0));
+		let policy = WivSupplyChain::policy(0).unwrap();
+		assert_eq!((policy.holder, policy.status), (4, PolicyStatus::Active));
+		assert_ok!(WivSupplyChain::file_claim(Origin::signed(4), 0, 100, H256::zero()));
+		// An environment breach suspends the coverage too.
+		let tolerance = EnvironmentTolerance {
+			min_temperature: 100,
+			max_temperature: 130,
+			min_humidity: 600,
+			max_humidity: 750,
+		};
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::set_tolerance(Origin::signed(4), 0, Some(tolerance)));
+		let (payload, signature) = telemetry(2, 5, vec![london_bond(128, 135)]);
+		assert_ok!(WivSupplyChain::submit_environment_unsigned(Origin::none(), payload, signature));
+		assert_eq!(
+			WivSupplyChain::policy(0).unwrap().status,
+			PolicyStatus::Suspended(SuspensionReason::EnvironmentBreach)
+		);
+		// A claim filed before the suspension can still be settled.
+		assert_ok!(WivSupplyChain::settle_claim(Origin::signed(6), 0, 100));
+		assert_eq!(WivSupplyChain::policy(0).unwrap().paid_out, 100);
+	});
+}
+
+#[test]
+fn policies_expire_and_release_the_coverage() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		let premium = PremiumSchedule { amount: 50, period: 30 };
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 500, premium, 20));
+		// Premiums don't cover the assets beyond the expiry of the policy.
+		assert_ok!(WivSupplyChain::pay_premium(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::policy(0).unwrap().paid_until, 20);
+		assert_noop!(WivSupplyChain::pay_premium(Origin::signed(1), 0), Error::<Test>::PremiumsPaid);
+		assert_ok!(WivSupplyChain::file_claim(Origin::signed(1), 0, 200, H256::zero()));
+		WivSupplyChain::on_initialize(20);
+		assert_eq!(WivSupplyChain::policy(0).unwrap().status, PolicyStatus::Expired);
+		assert!(WivSupplyChain::policies_of(0).is_empty());
+		// The coverage is released but the amount of the pending claim.
+		assert_eq!(Balances::reserved_balance(6), 200);
+		assert_noop!(WivSupplyChain::pay_premium(Origin::signed(1), 0), Error::<Test>::PolicyExpired);
+		assert_noop!(WivSupplyChain::file_claim(Origin::signed(1), 0, 100, H256::zero()), Error::<Test>::PolicyExpired);
+		assert_ok!(WivSupplyChain::settle_claim(Origin::signed(6), 0, 150));
+		assert_eq!(Balances::reserved_balance(6), 0);
+		assert_eq!(Balances::free_balance(6), 900);
+	});
+}
+
+#[test]
+fn issue_policy_checks_the_assets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(1), 1, vec![0], 500, premium(), 100),
+			Error::<Test>::MissingRole
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![], 500, premium(), 100),
+			Error::<Test>::EmptyBatch
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0, 0], 500, premium(), 100),
+			Error::<Test>::DuplicateAsset
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 2, vec![0], 500, premium(), 100),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![7], 500, premium(), 100),
+			Error::<Test>::UnknownAsset
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 0, premium(), 100),
+			Error::<Test>::InvalidAmount
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 500, PremiumSchedule { amount: 50, period: 0 }, 100),
+			Error::<Test>::InvalidPremium
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 500, premium(), 1),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 2_000, premium(), 100),
+			Error::<Test>::InsufficientCollateral
+		);
+		// An asset is covered by a limited number of policies.
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 100, premium(), 100));
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![0], 100, premium(), 100));
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![1, 0], 100, premium(), 101),
+			Error::<Test>::TooManyPolicies
+		);
+		// A limited number of policies expire in a block.
+		assert_noop!(
+			WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![1], 100, premium(), 100),
+			Error::<Test>::TooManyExpiries
+		);
+		assert_eq!(WivSupplyChain::next_policy_id(), 2);
+		assert_ok!(WivSupplyChain::issue_policy(Origin::signed(6), 1, vec![1], 100, premium(), 101));
+		assert_noop!(WivSupplyChain::pay_premium(Origin::signed(1), 5), Error::<Test>::UnknownPolicy);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn sale_pays_the_seller_the_producer_and_the_platform() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_eq!(WivSupplyChain::producer_of(0), Some(1));
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4));
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(4), 0, 600));
+		// The seller can change its price.
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(4), 0, 500));
+		assert_eq!(WivSupplyChain::listing(0).map(|l| (l.seller, l.price)), Some((4, 500)));
+		let (producer, seller) = (Balances::free_balance(1), Balances::free_balance(4));
+		assert_ok!(WivSupplyChain::buy(Origin::signed(7), 0));
+		// The platform takes 2% and the producer 5% of the price.
+		assert_eq!(Balances::free_balance(7), 500 - asset_deposit());
+		assert_eq!(Balances::free_balance(100), 1_010);
+		assert_eq!(Balances::free_balance(1), producer + 25);
+		assert_eq!(Balances::free_balance(4), seller + 465 + asset_deposit());
+		// The ownership moves with the payment, the buyer reserves the storage deposit.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(7));
+		assert_eq!(Balances::reserved_balance(7), asset_deposit());
+		assert_eq!(WivSupplyChain::listing(0), None);
+		assert_eq!(actions(0).last(), Some(&ProvenanceAction::Transferred));
+		assert_noop!(WivSupplyChain::buy(Origin::signed(8), 0), Error::<Test>::NotListed);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn buy_requires_funds() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 1_000));
+		assert_noop!(WivSupplyChain::buy(Origin::signed(1), 0), Error::<Test>::OwnListing);
+		// Accounts above 300 have no funds.
+		assert_noop!(WivSupplyChain::buy(Origin::signed(301), 0), Error::<Test>::InsufficientDeposit);
+		// The buyer's account must stay alive after the payment and the storage deposit.
+		assert_noop!(WivSupplyChain::buy(Origin::signed(7), 0), Error::<Test>::InsufficientFunds);
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(1));
+		assert_eq!(Balances::reserved_balance(7), 0);
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 999 - asset_deposit()));
+		assert_ok!(WivSupplyChain::buy(Origin::signed(7), 0));
+		assert_eq!(Balances::free_balance(7), 1);
+	});
+}
+
+#[test]
+fn listings_are_checked_and_withdrawn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(2), 0, 500), Error::<Test>::NotOwner);
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 0), Error::<Test>::InvalidAmount);
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 7, 500), Error::<Test>::UnknownAsset);
+		assert_ok!(WivSupplyChain::pack(Origin::signed(1), 0, vec![1]));
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 1, 500), Error::<Test>::AssetPacked);
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500));
+		assert_noop!(WivSupplyChain::delist(Origin::signed(2), 0), Error::<Test>::NotOwner);
+		assert_ok!(WivSupplyChain::delist(Origin::signed(1), 0));
+		assert_noop!(WivSupplyChain::delist(Origin::signed(1), 0), Error::<Test>::NotListed);
+		assert_noop!(WivSupplyChain::buy(Origin::signed(7), 0), Error::<Test>::NotListed);
+		// A listing doesn't survive a transfer.
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500));
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4));
+		assert_eq!(WivSupplyChain::listing(0), None);
+		// The packed assets are sold along with their parent.
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(4), 0, 500));
+		assert_ok!(WivSupplyChain::buy(Origin::signed(7), 0));
+		assert_eq!(WivSupplyChain::assets_of(&7), vec![0, 1]);
+	});
+}
+
+#[test]
+fn royalties_go_to_the_producer_of_the_source_lots() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 4, Role::Producer));
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 1, Role::Custodian));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), lot("A", 2)));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(4), lot("B", 1)));
+		// Split and merged lots of a single producer keep it.
+		assert_ok!(WivSupplyChain::split_asset(Origin::signed(1), 0, vec![AssetInput { data: lot("A", 1) }; 2]));
+		assert_eq!(WivSupplyChain::producer_of(2), Some(1));
+		assert_ok!(WivSupplyChain::merge_assets(Origin::signed(1), vec![2, 3], lot("A", 2)));
+		assert_eq!(WivSupplyChain::producer_of(4), Some(1));
+		assert_eq!(WivSupplyChain::producer_of(0), None);
+		// A blend of several producers has none.
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(4), 1, 1));
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(4), 1, 1));
+		assert_ok!(WivSupplyChain::merge_assets(Origin::signed(1), vec![4, 1], lot("AB", 3)));
+		assert_eq!(WivSupplyChain::producer_of(5), None);
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 5, 500));
+		let (seller, deposit) = (Balances::free_balance(1), WivSupplyChain::asset(5).unwrap().deposit);
+		assert_ok!(WivSupplyChain::buy(Origin::signed(7), 5));
+		assert_eq!(Balances::free_balance(1), seller + 490 + deposit);
+	});
+}
+
+#[test]
+fn english_auction_sells_to_the_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(4), 0, AuctionKind::English, 100, 10));
+		assert_eq!(WivSupplyChain::auction(0).map(|a| a.end), Some(11));
+		assert_noop!(WivSupplyChain::bid(Origin::signed(7), 0, 50), Error::<Test>::BidTooLow);
+		assert_noop!(WivSupplyChain::bid(Origin::signed(4), 0, 200), Error::<Test>::OwnListing);
+		assert_ok!(WivSupplyChain::bid(Origin::signed(7), 0, 200));
+		assert_eq!(Balances::reserved_balance(7), 200);
+		assert_noop!(WivSupplyChain::bid(Origin::signed(8), 0, 200), Error::<Test>::BidTooLow);
+		// An outbid bid is unreserved.
+		assert_ok!(WivSupplyChain::bid(Origin::signed(8), 0, 300));
+		assert_eq!(Balances::reserved_balance(7), 0);
+		assert_eq!(Balances::reserved_balance(8), 300);
+		// The highest bidder raising its bid only reserves the difference.
+		assert_ok!(WivSupplyChain::bid(Origin::signed(8), 0, 400));
+		assert_eq!(Balances::reserved_balance(8), 400);
+		assert_ok!(WivSupplyChain::bid(Origin::signed(7), 0, 500));
+		assert_eq!(Balances::reserved_balance(8), 0);
+		// The asset is locked during the auction.
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(4), 0, 5), Error::<Test>::InAuction);
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(4), 0, 600), Error::<Test>::InAuction);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(4), 0, RemovalReason::Consumed),
+			Error::<Test>::InAuction
+		);
+		System::set_block_number(11);
+		assert_noop!(WivSupplyChain::bid(Origin::signed(8), 0, 600), Error::<Test>::BiddingClosed);
+		let (producer, seller) = (Balances::free_balance(1), Balances::free_balance(4));
+		WivSupplyChain::on_finalize(11);
+		// The winning bid pays the same fees as a sale.
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(7));
+		assert_eq!(Balances::free_balance(7), 500 - asset_deposit());
+		assert_eq!(Balances::reserved_balance(7), asset_deposit());
+		assert_eq!(Balances::free_balance(100), 1_010);
+		assert_eq!(Balances::free_balance(1), producer + 25);
+		assert_eq!(Balances::free_balance(4), seller + 465 + asset_deposit());
+		assert_eq!(WivSupplyChain::auction(0), None);
+		assert_eq!(actions(0).last(), Some(&ProvenanceAction::Transferred));
+	});
+}
+
+#[test]
+fn sealed_bid_auction_sells_to_the_highest_revealed_bid() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::SealedBid, 100, 10));
+		// Bids are revealed during 5 blocks once the bidding has closed.
+		assert_eq!(WivSupplyChain::auction(0).map(|a| (a.bidding_end, a.end)), Some((11, 16)));
+		let salt = H256::repeat_byte(7);
+		let commit = |bidder: u64, amount: u64, deposit: u64| WivSupplyChain::commit_bid(
+			Origin::signed(bidder), 0, WivSupplyChain::bid_commitment(&bidder, amount, &salt), deposit
+		);
+		// The deposit hides the amount of the bid.
+		assert_ok!(commit(7, 300, 500));
+		assert_ok!(commit(8, 250, 250));
+		assert_ok!(commit(9, 400, 400));
+		assert_eq!(Balances::reserved_balance(7), 500);
+		assert_eq!(WivSupplyChain::auction(0).and_then(|a| a.highest_bid), None);
+		assert_noop!(WivSupplyChain::reveal_bid(Origin::signed(7), 0, 300, salt), Error::<Test>::NotRevealing);
+		System::set_block_number(11);
+		assert_noop!(commit(10, 300, 300), Error::<Test>::BiddingClosed);
+		assert_ok!(WivSupplyChain::reveal_bid(Origin::signed(8), 0, 250, salt));
+		assert_noop!(
+			WivSupplyChain::reveal_bid(Origin::signed(7), 0, 300, H256::repeat_byte(8)),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(WivSupplyChain::reveal_bid(Origin::signed(7), 0, 350, salt), Error::<Test>::InvalidReveal);
+		assert_ok!(WivSupplyChain::reveal_bid(Origin::signed(7), 0, 300, salt));
+		assert_noop!(WivSupplyChain::reveal_bid(Origin::signed(7), 0, 300, salt), Error::<Test>::AlreadyRevealed);
+		assert_noop!(WivSupplyChain::reveal_bid(Origin::signed(10), 0, 300, salt), Error::<Test>::NoBid);
+		assert_eq!(WivSupplyChain::auction(0).and_then(|a| a.highest_bid), Some((7, 300)));
+		// Bids not revealed in time can't win.
+		System::set_block_number(16);
+		assert_noop!(WivSupplyChain::reveal_bid(Origin::signed(9), 0, 400, salt), Error::<Test>::NotRevealing);
+		let producer = Balances::free_balance(1);
+		WivSupplyChain::on_finalize(16);
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(7));
+		// Only the winning bid is paid, the rest of the deposits is unreserved.
+		assert_eq!(Balances::free_balance(7), 700 - asset_deposit());
+		assert_eq!(Balances::reserved_balance(7), asset_deposit());
+		assert_eq!(Balances::free_balance(8), 1_000);
+		assert_eq!(Balances::free_balance(9), 1_000);
+		assert_eq!(Balances::free_balance(1), producer + 294 + asset_deposit());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(WivSupplyChain::sealed_bid(0, 8), None);
+	});
+}
+
+#[test]
+fn auctions_are_checked_and_cancelled() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(2), 0, AuctionKind::English, 100, 10),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 0),
+			Error::<Test>::InvalidDuration
+		);
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 21),
+			Error::<Test>::InvalidDuration
+		);
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 1, 500));
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 1, AuctionKind::English, 100, 10),
+			Error::<Test>::ForSale
+		);
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 20));
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::SealedBid, 100, 10),
+			Error::<Test>::InAuction
+		);
+		assert_noop!(
+			WivSupplyChain::commit_bid(Origin::signed(7), 0, H256::zero(), 100),
+			Error::<Test>::WrongAuctionKind
+		);
+		assert_noop!(WivSupplyChain::bid(Origin::signed(7), 2, 100), Error::<Test>::NoAuction);
+		// Accounts above 300 have no funds.
+		assert_noop!(WivSupplyChain::bid(Origin::signed(301), 0, 100), Error::<Test>::InsufficientFunds);
+		// The assets sold can't change.
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 0, vec![2]), Error::<Test>::InAuction);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![0]), Error::<Test>::InAuction);
+		assert_noop!(WivSupplyChain::cancel_auction(Origin::signed(2), 0), Error::<Test>::NotOwner);
+		assert_ok!(WivSupplyChain::bid(Origin::signed(7), 0, 100));
+		assert_noop!(WivSupplyChain::cancel_auction(Origin::signed(1), 0), Error::<Test>::HasBids);
+		// Sealed bids are limited.
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 2, AuctionKind::SealedBid, 0, 5));
+		assert_noop!(WivSupplyChain::bid(Origin::signed(8), 2, 100), Error::<Test>::WrongAuctionKind);
+		assert_noop!(
+			WivSupplyChain::commit_bid(Origin::signed(8), 2, H256::zero(), 0),
+			Error::<Test>::InvalidAmount
+		);
+		for bidder in 8..11 {
+			assert_ok!(WivSupplyChain::commit_bid(Origin::signed(bidder), 2, H256::zero(), 100));
+		}
+		assert_noop!(
+			WivSupplyChain::commit_bid(Origin::signed(8), 2, H256::zero(), 100),
+			Error::<Test>::AlreadyCommitted
+		);
+		assert_noop!(
+			WivSupplyChain::commit_bid(Origin::signed(11), 2, H256::zero(), 100),
+			Error::<Test>::TooManyBids
+		);
+		// Without a bid revealed, the asset stays with the seller and the deposits are unreserved.
+		WivSupplyChain::on_finalize(11);
+		assert_eq!(WivSupplyChain::asset(2).map(|a| a.owner), Some(1));
+		assert_eq!(WivSupplyChain::auction(2), None);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		// An auction without bids can be cancelled.
+		assert_ok!(WivSupplyChain::delist(Origin::signed(1), 1));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 1, AuctionKind::English, 100, 10));
+		assert_ok!(WivSupplyChain::cancel_auction(Origin::signed(1), 1));
+		assert_eq!(WivSupplyChain::auction(1), None);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 1, 4));
+		assert_noop!(WivSupplyChain::cancel_auction(Origin::signed(1), 1), Error::<Test>::NoAuction);
+	});
+}
+
+#[test]
+fn a_limited_number_of_auctions_end_in_a_block() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for _ in 0..3 {
+			assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		}
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 15));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 1, AuctionKind::SealedBid, 100, 10));
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 2, AuctionKind::English, 100, 15),
+			Error::<Test>::TooManyAuctions
+		);
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 2, AuctionKind::English, 100, 14));
+		// A cancelled auction frees its slot.
+		assert_ok!(WivSupplyChain::cancel_auction(Origin::signed(1), 2));
+		assert_ok!(WivSupplyChain::cancel_auction(Origin::signed(1), 0));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 2, AuctionKind::English, 100, 15));
+		WivSupplyChain::on_finalize(16);
+		assert_eq!(WivSupplyChain::auction(1), None);
+		assert_eq!(WivSupplyChain::auction(2), None);
+	});
+}
+
+#[test]
+fn shares_are_transferred_and_redeemed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::pack(Origin::signed(1), 0, vec![1]));
+		assert_noop!(WivSupplyChain::fractionalise(Origin::signed(2), 0, 10), Error::<Test>::NotOwner);
+		assert_noop!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 1), Error::<Test>::InvalidShares);
+		assert_noop!(WivSupplyChain::fractionalise(Origin::signed(1), 1, 10), Error::<Test>::AssetPacked);
+		assert_ok!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 10));
+		assert_noop!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 10), Error::<Test>::Fractionalised);
+		assert_eq!(WivSupplyChain::shares(0, 1), 10);
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 6));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 7, 3));
+		assert_noop!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 2), Error::<Test>::InsufficientShares);
+		assert_noop!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 0), Error::<Test>::InvalidAmount);
+		let mut holders = WivSupplyChain::share_holders(0);
+		holders.sort();
+		assert_eq!(holders, vec![(1, 1), (4, 6), (7, 3)]);
+		// The asset and the assets packed in it are locked while shares are outstanding.
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4), Error::<Test>::Fractionalised);
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500), Error::<Test>::Fractionalised);
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 0, AuctionKind::English, 100, 10),
+			Error::<Test>::Fractionalised
+		);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed),
+			Error::<Test>::Fractionalised
+		);
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 0), Error::<Test>::Fractionalised);
+		// Only the holder of every share redeems the asset.
+		assert_noop!(WivSupplyChain::redeem(Origin::signed(4), 0), Error::<Test>::MissingShares);
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(7), 0, 4, 3));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 1));
+		assert_eq!(WivSupplyChain::share_holders(0), vec![(4, 10)]);
+		assert_ok!(WivSupplyChain::redeem(Origin::signed(4), 0));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(4));
+		assert_eq!(WivSupplyChain::asset(1).map(|a| a.owner), Some(4));
+		assert_eq!(Balances::reserved_balance(4), 2 * asset_deposit());
+		assert_eq!(WivSupplyChain::share_supply(0), None);
+		assert_eq!(WivSupplyChain::share_holders(0), vec![]);
+		assert_eq!(actions(0), vec![
+			ProvenanceAction::Created,
+			ProvenanceAction::Fractionalised(10),
+			ProvenanceAction::Redeemed,
+			ProvenanceAction::Transferred,
+		]);
+		assert_noop!(WivSupplyChain::transfer_shares(Origin::signed(4), 0, 1, 1), Error::<Test>::NotFractionalised);
+		assert_noop!(WivSupplyChain::redeem(Origin::signed(4), 0), Error::<Test>::NotFractionalised);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(4), 0, 1));
+	});
+}
+
+#[test]
+fn owner_redeeming_every_share_keeps_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 4, 1));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(4), 0, 1, 1));
+		assert_ok!(WivSupplyChain::redeem(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(1));
+		assert_eq!(actions(0).last(), Some(&ProvenanceAction::Redeemed));
+	});
+}
+
+#[test]
+fn failed_redemption_keeps_the_shares() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::fractionalise(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::transfer_shares(Origin::signed(1), 0, 301, 2));
+		// Accounts above 300 can't reserve the storage deposit of the asset.
+		assert_noop!(WivSupplyChain::redeem(Origin::signed(301), 0), Error::<Test>::InsufficientDeposit);
+		assert_eq!(WivSupplyChain::share_supply(0), Some(2));
+		assert_eq!(WivSupplyChain::share_holders(0), vec![(301, 2)]);
+		assert_eq!(WivSupplyChain::asset(0).map(|a| a.owner), Some(1));
+	});
+}
+
+#[test]
+fn status_follows_the_lifecycle_of_the_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::Registered));
+		// A withdrawn handover or sale restores the previous status.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::InTransit));
+		assert_ok!(WivSupplyChain::cancel_offer(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::Registered));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_ok!(WivSupplyChain::accept_transfer(Origin::signed(2), 0));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::InStorage));
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::Listed));
+		assert_ok!(WivSupplyChain::delist(Origin::signed(1), 0));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::InStorage));
+		// The status of a removed asset is kept by its tombstone when the removal is final.
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 1, RemovalReason::Withdrawn));
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::Consumed));
+		assert_eq!(WivSupplyChain::status_of(1), None);
+		assert_eq!(WivSupplyChain::status_of(2), None);
+	});
+}
+
+#[test]
+fn invalid_status_transitions_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		// An asset in transit can't be listed, a listed asset can't be handed over.
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(1), 0, 2, None));
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500), Error::<Test>::InvalidStateTransition);
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 1, 500));
+		assert_noop!(
+			WivSupplyChain::offer_transfer(Origin::signed(1), 1, 2, None),
+			Error::<Test>::InvalidStateTransition
+		);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(1), 1, 2), Error::<Test>::InvalidStateTransition);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 2, vec![1]), Error::<Test>::InvalidStateTransition);
+		// A lost asset is only destroyed or found.
+		assert_ok!(WivSupplyChain::report_lost(Origin::signed(1), 2));
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 2, 500), Error::<Test>::InvalidStateTransition);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 2, RemovalReason::Consumed),
+			Error::<Test>::InvalidStateTransition
+		);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 2, RemovalReason::Withdrawn),
+			Error::<Test>::InvalidStateTransition
+		);
+		assert_noop!(WivSupplyChain::report_lost(Origin::signed(1), 2), Error::<Test>::InvalidStateTransition);
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 2, RemovalReason::Destroyed));
+		assert_eq!(WivSupplyChain::status_of(2), Some(AssetStatus::Destroyed));
+	});
+}
+
+#[test]
+fn lost_assets_are_reported_and_found() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		assert_ok!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2));
+		assert_ok!(WivSupplyChain::offer_transfer(Origin::signed(2), 0, 3, None));
+		assert_noop!(WivSupplyChain::report_lost(Origin::signed(3), 0), Error::<Test>::NotCustodian);
+		assert_noop!(WivSupplyChain::report_lost(Origin::signed(2), 1), Error::<Test>::AssetPacked);
+		assert_noop!(WivSupplyChain::report_found(Origin::signed(2), 0), Error::<Test>::NotLost);
+		// The pending handover is cancelled and the packed assets are lost with the pallet.
+		assert_ok!(WivSupplyChain::report_lost(Origin::signed(2), 0));
+		assert_eq!(WivSupplyChain::offer(0), None);
+		for id in 0..6 {
+			assert_eq!(WivSupplyChain::status_of(id), Some(AssetStatus::Lost));
+		}
+		assert_eq!(WivSupplyChain::status_of(6), Some(AssetStatus::Registered));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 0), Error::<Test>::InvalidStateTransition);
+		// A lost asset can't be handed over, only its last custodian or its owner reports it found.
+		assert_noop!(
+			WivSupplyChain::transfer_custody(Origin::signed(2), 0, 3),
+			Error::<Test>::InvalidStateTransition
+		);
+		// Nor can it change owner or be inspected.
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4), Error::<Test>::InvalidStateTransition);
+		assert_noop!(
+			WivSupplyChain::attest(Origin::signed(5), 3, AttestationKind::Label, H256::repeat_byte(1), 90),
+			Error::<Test>::InvalidStateTransition
+		);
+		assert_noop!(WivSupplyChain::report_found(Origin::signed(3), 0), Error::<Test>::NotCustodian);
+		assert_ok!(WivSupplyChain::report_found(Origin::signed(1), 0));
+		for id in 0..6 {
+			let details = WivSupplyChain::asset(id).unwrap();
+			assert_eq!((details.custodian, details.status), (1, AssetStatus::InStorage));
+		}
+		assert_eq!(WivSupplyChain::assets_in_custody(&1).len(), 7);
+		assert!(WivSupplyChain::assets_in_custody(&2).is_empty());
+		assert_eq!(actions(0), vec![
+			ProvenanceAction::Created,
+			ProvenanceAction::Stored,
+			ProvenanceAction::Lost,
+			ProvenanceAction::Stored,
+		]);
+	});
+}
+
+#[test]
+fn frozen_assets_are_locked_until_thawed() {
+	new_test_ext().execute_with(|| {
+		pack_pallet();
+		assert_noop!(
+			WivSupplyChain::freeze_asset(Origin::signed(1), 6, FreezeReason::Fraud),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(WivSupplyChain::freeze_asset(Origin::root(), 7, FreezeReason::Fraud), Error::<Test>::UnknownAsset);
+		assert_ok!(WivSupplyChain::freeze_asset(Origin::root(), 6, FreezeReason::Fraud));
+		assert_noop!(WivSupplyChain::freeze_asset(Origin::root(), 6, FreezeReason::Seizure), Error::<Test>::Frozen);
+		assert_eq!(WivSupplyChain::frozen(6), Some(FreezeReason::Fraud));
+		// Neither the owner nor the custodian can move a frozen asset.
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(1), 6, 4), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::list_for_sale(Origin::signed(1), 6, 500), Error::<Test>::Frozen);
+		assert_noop!(
+			WivSupplyChain::start_auction(Origin::signed(1), 6, AuctionKind::English, 100, 10),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(WivSupplyChain::fractionalise(Origin::signed(1), 6, 10), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(1), 6, 2), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::offer_transfer(Origin::signed(1), 6, 2, None), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::report_lost(Origin::signed(1), 6), Error::<Test>::Frozen);
+		assert_noop!(
+			WivSupplyChain::remove_asset(Origin::signed(1), 6, RemovalReason::Consumed),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(WivSupplyChain::pack(Origin::signed(1), 0, vec![6]), Error::<Test>::Frozen);
+		assert_noop!(
+			WivSupplyChain::set_tolerance(Origin::signed(1), 6, Some(EnvironmentTolerance::default())),
+			Error::<Test>::Frozen
+		);
+		// A frozen bottle locks the case and the pallet it is packed in.
+		assert_ok!(WivSupplyChain::freeze_asset(Origin::root(), 3, FreezeReason::Counterfeit));
+		assert_noop!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::transfer_custody(Origin::signed(1), 0, 2), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::Frozen);
+		assert_ok!(WivSupplyChain::thaw_asset(Origin::root(), 3));
+		assert_noop!(WivSupplyChain::thaw_asset(Origin::root(), 3), Error::<Test>::NotFrozen);
+		// A frozen pallet locks the cases and bottles packed in it.
+		assert_ok!(WivSupplyChain::freeze_asset(Origin::root(), 0, FreezeReason::Seizure));
+		assert_noop!(WivSupplyChain::unpack(Origin::signed(1), 1), Error::<Test>::Frozen);
+		assert_noop!(
+			WivSupplyChain::set_tolerance(Origin::signed(1), 3, Some(EnvironmentTolerance::default())),
+			Error::<Test>::Frozen
+		);
+		assert_ok!(WivSupplyChain::thaw_asset(Origin::root(), 0));
+		assert_noop!(WivSupplyChain::thaw_asset(Origin::signed(1), 6), DispatchError::BadOrigin);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 0, 4));
+		assert_ok!(WivSupplyChain::thaw_asset(Origin::root(), 6));
+		assert_eq!(WivSupplyChain::frozen(6), None);
+		assert_ok!(WivSupplyChain::transfer_asset(Origin::signed(1), 6, 4));
+	});
+}
+
+#[test]
+fn frozen_assets_are_not_sold() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::list_for_sale(Origin::signed(1), 0, 500));
+		assert_ok!(WivSupplyChain::start_auction(Origin::signed(1), 1, AuctionKind::English, 100, 10));
+		assert_ok!(WivSupplyChain::bid(Origin::signed(7), 1, 200));
+		assert_ok!(WivSupplyChain::freeze_asset(Origin::root(), 0, FreezeReason::Seizure));
+		assert_ok!(WivSupplyChain::freeze_asset(Origin::root(), 1, FreezeReason::Seizure));
+		assert_noop!(WivSupplyChain::buy(Origin::signed(7), 0), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::bid(Origin::signed(8), 1, 300), Error::<Test>::Frozen);
+		assert_noop!(WivSupplyChain::cancel_auction(Origin::signed(1), 1), Error::<Test>::Frozen);
+		// Nor can the seller withdraw the listing.
+		assert_noop!(WivSupplyChain::delist(Origin::signed(1), 0), Error::<Test>::Frozen);
+		assert_eq!(WivSupplyChain::status_of(0), Some(AssetStatus::Listed));
+		// The auction of a frozen asset closes without a winner and the bid is given back.
+		WivSupplyChain::on_finalize(11);
+		assert_eq!(WivSupplyChain::auction(1), None);
+		assert_eq!(WivSupplyChain::asset(1).map(|a| (a.owner, a.status)), Some((1, AssetStatus::Registered)));
+		assert_eq!(Balances::reserved_balance(7), 0);
+	});
+}
+
+#[test]
+fn recall_freezes_the_assets_of_a_producer_or_lot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), ASSET.to_vec()));
+		let krug = encode(r#"{"producer":"Krug","format":"1500ml","lot":"K-7","location":"Reims"}"#);
+		assert_ok!(WivSupplyChain::new_asset(Origin::signed(1), krug));
+		assert_ok!(WivSupplyChain::grant_role(Origin::root(), 4, Role::Producer));
+		for _ in 0..4 {
+			assert_ok!(WivSupplyChain::new_asset(Origin::signed(4), ASSET.to_vec()));
+		}
+		let (margaux, krug) = (b"Chateau Margaux".to_vec(), b"Krug".to_vec());
+		let lot = Some(b"L15-042".to_vec());
+		assert_noop!(
+			WivSupplyChain::recall(Origin::signed(1), margaux.clone(), lot.clone(), FreezeReason::Recall),
+			DispatchError::BadOrigin
+		);
+		// The producer named in the asset data is recalled, whoever registered the asset.
+		assert_ok!(WivSupplyChain::recall(Origin::root(), margaux.clone(), lot.clone(), FreezeReason::Recall));
+		let frozen = |ids: std::ops::Range<u64>| ids.filter(|&id| WivSupplyChain::frozen(id).is_some()).count();
+		assert_eq!(frozen(0..7), 3);
+		assert_eq!(WivSupplyChain::frozen(2), None);
+		// Large recalls are repeated, 3 assets at most are frozen by a call.
+		assert_ok!(WivSupplyChain::recall(Origin::root(), margaux.clone(), lot.clone(), FreezeReason::Recall));
+		assert_eq!(frozen(0..7), 6);
+		assert_noop!(
+			WivSupplyChain::recall(Origin::root(), margaux.clone(), None, FreezeReason::Recall),
+			Error::<Test>::NothingToRecall
+		);
+		// Only the lot of the given producer is recalled.
 		assert_noop!(
-			TemplateModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
+			WivSupplyChain::recall(Origin::root(), krug.clone(), lot.clone(), FreezeReason::Recall),
+			Error::<Test>::NothingToRecall
 		);
+		assert_ok!(WivSupplyChain::recall(Origin::root(), krug, None, FreezeReason::Counterfeit));
+		assert_eq!(WivSupplyChain::frozen(2), Some(FreezeReason::Counterfeit));
+		// Thawed assets can be recalled again, removed assets leave the recall indices.
+		assert_ok!(WivSupplyChain::thaw_asset(Origin::root(), 0));
+		assert_ok!(WivSupplyChain::thaw_asset(Origin::root(), 1));
+		assert_ok!(WivSupplyChain::remove_asset(Origin::signed(1), 0, RemovalReason::Consumed));
+		assert_ok!(WivSupplyChain::recall(Origin::root(), margaux, lot, FreezeReason::Counterfeit));
+		assert_eq!(WivSupplyChain::frozen(0), None);
+		assert_eq!(WivSupplyChain::frozen(1), Some(FreezeReason::Counterfeit));
 	});
 }
@@ -0,0 +1,658 @@
+//! Weights for pallet_wivsupplychain
+//!
+//! Refresh them on the reference hardware by running the benchmarks in `benchmarking.rs`:
+//! ./target/release/node-wivsupplychain benchmark --chain dev --execution wasm
+//! --wasm-execution compiled --pallet pallet_wivsupplychain --extrinsic '*'
+//! --steps 50 --repeat 20
+//!
+//! The values below have not been produced by this command yet, they are estimates to be replaced
+//! by its output.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_wivsupplychain.
+pub trait WeightInfo {
+	fn new_asset(b: u32, ) -> Weight;
+	fn new_anchored_asset(u: u32, ) -> Weight;
+	fn new_assets_batch(n: u32, ) -> Weight;
+	fn transfer_asset(n: u32, b: u32, ) -> Weight;
+	fn list_for_sale() -> Weight;
+	fn delist() -> Weight;
+	fn buy(n: u32, ) -> Weight;
+	fn start_auction() -> Weight;
+	fn bid() -> Weight;
+	fn commit_bid() -> Weight;
+	fn reveal_bid() -> Weight;
+	fn cancel_auction() -> Weight;
+	fn fractionalise() -> Weight;
+	fn transfer_shares() -> Weight;
+	fn redeem(n: u32, ) -> Weight;
+	fn remove_asset(b: u32, ) -> Weight;
+	fn split_asset(n: u32, ) -> Weight;
+	fn merge_assets(n: u32, b: u32, ) -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
+	fn freeze_asset() -> Weight;
+	fn thaw_asset() -> Weight;
+	fn recall(n: u32, ) -> Weight;
+	fn transfer_custody(n: u32, ) -> Weight;
+	fn offer_transfer() -> Weight;
+	fn accept_transfer(n: u32, ) -> Weight;
+	fn reject_transfer() -> Weight;
+	fn cancel_offer() -> Weight;
+	fn report_lost(n: u32, ) -> Weight;
+	fn report_found(n: u32, ) -> Weight;
+	fn attest() -> Weight;
+	fn set_tolerance() -> Weight;
+	fn submit_environment_unsigned(r: u32, a: u32, ) -> Weight;
+	fn pack(n: u32, ) -> Weight;
+	fn unpack(n: u32, ) -> Weight;
+	fn register_encryption_key() -> Weight;
+	fn set_confidential_metadata(b: u32, r: u32, ) -> Weight;
+	fn grant_access(r: u32, ) -> Weight;
+	fn revoke_access(r: u32, ) -> Weight;
+	fn issue_policy(n: u32, ) -> Weight;
+	fn pay_premium() -> Weight;
+	fn reinstate_policy(n: u32, ) -> Weight;
+	fn file_claim() -> Weight;
+	fn settle_claim() -> Weight;
+	fn on_initialize(n: u32, p: u32, ) -> Weight;
+	fn on_finalize(a: u32, ) -> Weight;
+}
+
+/// Weights for pallet_wivsupplychain using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn new_asset(b: u32, ) -> Weight {
+		(46_212_000 as Weight)
+			.saturating_add((5_873 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn new_anchored_asset(u: u32, ) -> Weight {
+		(43_870_000 as Weight)
+			.saturating_add((2_416 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	fn new_assets_batch(n: u32, ) -> Weight {
+		(18_734_000 as Weight)
+			.saturating_add((94_561_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_asset(n: u32, b: u32, ) -> Weight {
+		(38_604_000 as Weight)
+			.saturating_add((29_873_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((4_127 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn list_for_sale() -> Weight {
+		(27_319_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn delist() -> Weight {
+		(21_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn buy(n: u32, ) -> Weight {
+		(97_652_000 as Weight)
+			.saturating_add((30_114_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn start_auction() -> Weight {
+		(45_310_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn bid() -> Weight {
+		(58_240_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn commit_bid() -> Weight {
+		(52_760_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn reveal_bid() -> Weight {
+		(49_180_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(24_930_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn fractionalise() -> Weight {
+		(38_420_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn transfer_shares() -> Weight {
+		(31_570_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn redeem(n: u32, ) -> Weight {
+		(71_930_000 as Weight)
+			.saturating_add((30_114_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn remove_asset(b: u32, ) -> Weight {
+		(40_117_000 as Weight)
+			.saturating_add((2_964 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn split_asset(n: u32, ) -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add((97_862_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn merge_assets(n: u32, b: u32, ) -> Weight {
+		(44_960_000 as Weight)
+			.saturating_add((31_207_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((5_873 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	fn grant_role() -> Weight {
+		(21_345_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_role() -> Weight {
+		(21_102_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn freeze_asset() -> Weight {
+		(24_187_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn thaw_asset() -> Weight {
+		(22_054_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn recall(n: u32, ) -> Weight {
+		(19_872_000 as Weight)
+			.saturating_add((9_213_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_custody(n: u32, ) -> Weight {
+		(33_940_000 as Weight)
+			.saturating_add((21_455_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn offer_transfer() -> Weight {
+		(32_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn accept_transfer(n: u32, ) -> Weight {
+		(39_842_000 as Weight)
+			.saturating_add((21_455_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn reject_transfer() -> Weight {
+		(22_408_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_offer() -> Weight {
+		(21_985_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn report_lost(n: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add((14_026_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn report_found(n: u32, ) -> Weight {
+		(41_873_000 as Weight)
+			.saturating_add((21_390_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn attest() -> Weight {
+		(35_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn set_tolerance() -> Weight {
+		(31_264_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn submit_environment_unsigned(r: u32, a: u32, ) -> Weight {
+		(18_553_000 as Weight)
+			.saturating_add((12_087_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((19_768_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	fn pack(n: u32, ) -> Weight {
+		(31_508_000 as Weight)
+			.saturating_add((6_214_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn unpack(n: u32, ) -> Weight {
+		(24_096_000 as Weight)
+			.saturating_add((2_837_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn register_encryption_key() -> Weight {
+		(19_874_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_confidential_metadata(b: u32, r: u32, ) -> Weight {
+		(41_332_000 as Weight)
+			.saturating_add((2_105 as Weight).saturating_mul(b as Weight))
+			.saturating_add((1_412_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn grant_access(r: u32, ) -> Weight {
+		(43_519_000 as Weight)
+			.saturating_add((387_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn revoke_access(r: u32, ) -> Weight {
+		(38_206_000 as Weight)
+			.saturating_add((362_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn issue_policy(n: u32, ) -> Weight {
+		(44_318_000 as Weight)
+			.saturating_add((9_871_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn pay_premium() -> Weight {
+		(61_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn reinstate_policy(n: u32, ) -> Weight {
+		(27_936_000 as Weight)
+			.saturating_add((4_722_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn file_claim() -> Weight {
+		(30_158_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn settle_claim() -> Weight {
+		(58_477_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn on_initialize(n: u32, p: u32, ) -> Weight {
+		(3_120_000 as Weight)
+			.saturating_add((14_211_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((31_842_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().reads((7 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(p as Weight)))
+	}
+	fn on_finalize(a: u32, ) -> Weight {
+		(2_870_000 as Weight)
+			.saturating_add((412_560_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((44 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((42 as Weight).saturating_mul(a as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn new_asset(b: u32, ) -> Weight {
+		(46_212_000 as Weight)
+			.saturating_add((5_873 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn new_anchored_asset(u: u32, ) -> Weight {
+		(43_870_000 as Weight)
+			.saturating_add((2_416 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn new_assets_batch(n: u32, ) -> Weight {
+		(18_734_000 as Weight)
+			.saturating_add((94_561_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_asset(n: u32, b: u32, ) -> Weight {
+		(38_604_000 as Weight)
+			.saturating_add((29_873_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((4_127 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn list_for_sale() -> Weight {
+		(27_319_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn delist() -> Weight {
+		(21_874_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn buy(n: u32, ) -> Weight {
+		(97_652_000 as Weight)
+			.saturating_add((30_114_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn start_auction() -> Weight {
+		(45_310_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn bid() -> Weight {
+		(58_240_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn commit_bid() -> Weight {
+		(52_760_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn reveal_bid() -> Weight {
+		(49_180_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_auction() -> Weight {
+		(24_930_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn fractionalise() -> Weight {
+		(38_420_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn transfer_shares() -> Weight {
+		(31_570_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn redeem(n: u32, ) -> Weight {
+		(71_930_000 as Weight)
+			.saturating_add((30_114_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn remove_asset(b: u32, ) -> Weight {
+		(40_117_000 as Weight)
+			.saturating_add((2_964 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn split_asset(n: u32, ) -> Weight {
+		(22_417_000 as Weight)
+			.saturating_add((97_862_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn merge_assets(n: u32, b: u32, ) -> Weight {
+		(44_960_000 as Weight)
+			.saturating_add((31_207_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((5_873 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((8 as Weight).saturating_mul(n as Weight)))
+	}
+	fn grant_role() -> Weight {
+		(21_345_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_role() -> Weight {
+		(21_102_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn freeze_asset() -> Weight {
+		(24_187_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn thaw_asset() -> Weight {
+		(22_054_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn recall(n: u32, ) -> Weight {
+		(19_872_000 as Weight)
+			.saturating_add((9_213_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn transfer_custody(n: u32, ) -> Weight {
+		(33_940_000 as Weight)
+			.saturating_add((21_455_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn offer_transfer() -> Weight {
+		(32_871_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn accept_transfer(n: u32, ) -> Weight {
+		(39_842_000 as Weight)
+			.saturating_add((21_455_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn reject_transfer() -> Weight {
+		(22_408_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_offer() -> Weight {
+		(21_985_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn report_lost(n: u32, ) -> Weight {
+		(38_412_000 as Weight)
+			.saturating_add((14_026_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn report_found(n: u32, ) -> Weight {
+		(41_873_000 as Weight)
+			.saturating_add((21_390_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(n as Weight)))
+	}
+	fn attest() -> Weight {
+		(35_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn set_tolerance() -> Weight {
+		(31_264_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn submit_environment_unsigned(r: u32, a: u32, ) -> Weight {
+		(18_553_000 as Weight)
+			.saturating_add((12_087_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((19_768_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	fn pack(n: u32, ) -> Weight {
+		(31_508_000 as Weight)
+			.saturating_add((6_214_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn unpack(n: u32, ) -> Weight {
+		(24_096_000 as Weight)
+			.saturating_add((2_837_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn register_encryption_key() -> Weight {
+		(19_874_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_confidential_metadata(b: u32, r: u32, ) -> Weight {
+		(41_332_000 as Weight)
+			.saturating_add((2_105 as Weight).saturating_mul(b as Weight))
+			.saturating_add((1_412_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn grant_access(r: u32, ) -> Weight {
+		(43_519_000 as Weight)
+			.saturating_add((387_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn revoke_access(r: u32, ) -> Weight {
+		(38_206_000 as Weight)
+			.saturating_add((362_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn issue_policy(n: u32, ) -> Weight {
+		(44_318_000 as Weight)
+			.saturating_add((9_871_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn pay_premium() -> Weight {
+		(61_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn reinstate_policy(n: u32, ) -> Weight {
+		(27_936_000 as Weight)
+			.saturating_add((4_722_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn file_claim() -> Weight {
+		(30_158_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn settle_claim() -> Weight {
+		(58_477_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn on_initialize(n: u32, p: u32, ) -> Weight {
+		(3_120_000 as Weight)
+			.saturating_add((14_211_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((31_842_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((7 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(p as Weight)))
+	}
+	fn on_finalize(a: u32, ) -> Weight {
+		(2_870_000 as Weight)
+			.saturating_add((412_560_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((44 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((42 as Weight).saturating_mul(a as Weight)))
+	}
+}
@@ -9,11 +9,11 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
-	transaction_validity::{TransactionValidity, TransactionSource},
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, ModuleId,
+	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 };
 use sp_runtime::traits::{
-	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -62,6 +62,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -222,7 +225,7 @@ parameter_types! {
 
 impl pallet_timestamp::Trait for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -267,9 +270,81 @@ impl pallet_template::Trait for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MinAssetLength: u32 = 16;
+	pub const MaxAssetLength: u32 = 8192;
+	pub const MaxUriLength: u32 = 256;
+	pub const MaxBatchSize: u32 = 100;
+	pub const MaxRecipients: u32 = 16;
+	// pallets of cases of bottles
+	pub const MaxNestingDepth: u32 = 2;
+	pub const MaxChildren: u32 = 24;
+	pub const AssetDepositBase: Balance = 10_000;
+	pub const AssetDepositPerByte: Balance = 100;
+	pub const OfferExpiry: BlockNumber = 7 * DAYS;
+	pub const AttestationValidity: BlockNumber = 30 * DAYS;
+	pub const MaxPolicies: u32 = 8;
+	pub const MaxPolicyExpiries: u32 = 100;
+	pub const MarketplaceFee: Permill = Permill::from_percent(2);
+	pub const RoyaltyFee: Permill = Permill::from_percent(5);
+	pub const MarketplaceModuleId: ModuleId = ModuleId(*b"wiv/mrkt");
+	pub MarketplaceAccount: AccountId = MarketplaceModuleId::get().into_account();
+	pub const MaxAuctionDuration: BlockNumber = 30 * DAYS;
+	pub const RevealPeriod: BlockNumber = 2 * DAYS;
+	pub const MaxSealedBids: u32 = 32;
+	pub const MaxAuctionsPerBlock: u32 = 10;
+	pub const MaxRecall: u32 = 500;
+	pub const TelemetryInterval: BlockNumber = 10 * MINUTES;
+	pub const MaxReadings: u32 = 16;
+	pub const MaxMonitoredAssets: u32 = 100;
+	pub const TelemetryPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+	// entries returned by a page of the history or attestations queries
+	pub const MaxPageSize: u32 = 100;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime where Call: From<C> {
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
 /// Configure the wivsupplychain pallet in pallets/wivsupplychain
 impl pallet_wivsupplychain::Trait for Runtime {
 	type Event = Event;
+	type AuthorityId = pallet_wivsupplychain::crypto::TelemetryAuthId;
+	type TelemetryInterval = TelemetryInterval;
+	type MaxReadings = MaxReadings;
+	type MaxMonitoredAssets = MaxMonitoredAssets;
+	type UnsignedPriority = TelemetryPriority;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxRecall = MaxRecall;
+	type OfferExpiry = OfferExpiry;
+	type AttestationValidity = AttestationValidity;
+	type MaxPolicies = MaxPolicies;
+	type MaxPolicyExpiries = MaxPolicyExpiries;
+	type MarketplaceFee = MarketplaceFee;
+	type RoyaltyFee = RoyaltyFee;
+	type PlatformAccount = MarketplaceAccount;
+	type MaxAuctionDuration = MaxAuctionDuration;
+	type RevealPeriod = RevealPeriod;
+	type MaxSealedBids = MaxSealedBids;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MinAssetLength = MinAssetLength;
+	type MaxAssetLength = MaxAssetLength;
+	type MaxUriLength = MaxUriLength;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxRecipients = MaxRecipients;
+	type MaxNestingDepth = MaxNestingDepth;
+	type MaxChildren = MaxChildren;
+	type Currency = Balances;
+	type AssetDepositBase = AssetDepositBase;
+	type AssetDepositPerByte = AssetDepositPerByte;
+	type WeightInfo = pallet_wivsupplychain::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -290,7 +365,7 @@ construct_runtime!(
 		// Include the custom logic from the template pallet in the runtime.
 		TemplateModule: pallet_template::{Module, Call, Storage, Event<T>},
 		// Wiv Supply Chain Pallet
-		WivSupplyChain: pallet_wivsupplychain::{Module, Call, Storage, Event<T>},
+		WivSupplyChain: pallet_wivsupplychain::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned},
 
 	}
 );
@@ -452,6 +527,104 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_wivsupplychain_rpc_runtime_api::WivSupplyChainApi<
+		Block,
+		AccountId,
+		Balance,
+		BlockNumber,
+		Moment,
+	> for Runtime {
+		fn asset(
+			asset_id: pallet_wivsupplychain::AssetId,
+		) -> Option<pallet_wivsupplychain::AssetDetails<AccountId, Balance>> {
+			WivSupplyChain::asset(asset_id)
+		}
+
+		fn assets_by_owner(owner: AccountId) -> Vec<pallet_wivsupplychain::AssetId> {
+			WivSupplyChain::assets_of(&owner)
+		}
+
+		fn assets_by_custodian(custodian: AccountId) -> Vec<pallet_wivsupplychain::AssetId> {
+			WivSupplyChain::assets_in_custody(&custodian)
+		}
+
+		fn history(
+			asset_id: pallet_wivsupplychain::AssetId,
+			page: u32,
+			page_size: u32,
+		) -> Vec<pallet_wivsupplychain::ProvenanceEntry<AccountId, BlockNumber, Moment>> {
+			WivSupplyChain::history(asset_id, page, page_size.min(MaxPageSize::get()))
+		}
+
+		fn attestations(
+			asset_id: pallet_wivsupplychain::AssetId,
+			page: u32,
+			page_size: u32,
+		) -> Vec<pallet_wivsupplychain::Attestation<AccountId, BlockNumber, Moment>> {
+			WivSupplyChain::attestations(asset_id, page, page_size.min(MaxPageSize::get()))
+		}
+
+		fn latest_attestations(
+			asset_id: pallet_wivsupplychain::AssetId,
+		) -> Vec<pallet_wivsupplychain::Attestation<AccountId, BlockNumber, Moment>> {
+			WivSupplyChain::latest_attestations(asset_id)
+		}
+
+		fn tree(asset_id: pallet_wivsupplychain::AssetId) -> Option<pallet_wivsupplychain::AssetTree> {
+			WivSupplyChain::tree(asset_id)
+		}
+
+		fn ancestors(asset_id: pallet_wivsupplychain::AssetId) -> Vec<pallet_wivsupplychain::AssetId> {
+			WivSupplyChain::ancestors(asset_id)
+		}
+
+		fn lineage(asset_id: pallet_wivsupplychain::AssetId) -> Vec<pallet_wivsupplychain::AssetId> {
+			WivSupplyChain::lineage(asset_id)
+		}
+
+		fn verify_asset_document(asset_id: pallet_wivsupplychain::AssetId, document: Vec<u8>) -> bool {
+			WivSupplyChain::verify_document(asset_id, &document)
+		}
+
+		fn confidential_metadata(
+			asset_id: pallet_wivsupplychain::AssetId,
+		) -> Option<pallet_wivsupplychain::ConfidentialMetadata<AccountId, Balance>> {
+			WivSupplyChain::confidential(asset_id)
+		}
+
+		fn encryption_key(account: AccountId) -> Option<pallet_wivsupplychain::EncryptionKey> {
+			WivSupplyChain::encryption_key(&account)
+		}
+
+		fn policy(
+			policy_id: pallet_wivsupplychain::PolicyId,
+		) -> Option<pallet_wivsupplychain::Policy<AccountId, Balance, BlockNumber>> {
+			WivSupplyChain::policy(policy_id)
+		}
+
+		fn asset_policies(asset_id: pallet_wivsupplychain::AssetId) -> Vec<pallet_wivsupplychain::PolicyId> {
+			WivSupplyChain::policies_of(asset_id)
+		}
+
+		fn auction(
+			asset_id: pallet_wivsupplychain::AssetId,
+		) -> Option<pallet_wivsupplychain::Auction<AccountId, Balance, BlockNumber>> {
+			WivSupplyChain::auction(asset_id)
+		}
+
+		fn share_holders(asset_id: pallet_wivsupplychain::AssetId) -> Vec<(AccountId, u64)> {
+			WivSupplyChain::share_holders(asset_id)
+		}
+
+		fn asset_status(asset_id: pallet_wivsupplychain::AssetId) -> Option<pallet_wivsupplychain::AssetStatus> {
+			WivSupplyChain::status_of(asset_id)
+		}
+
+		fn freeze_reason(asset_id: pallet_wivsupplychain::AssetId) -> Option<pallet_wivsupplychain::FreezeReason> {
+			WivSupplyChain::frozen(asset_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(
@@ -481,6 +654,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+			add_benchmark!(params, batches, pallet_wivsupplychain, WivSupplyChain);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)